@@ -0,0 +1,150 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::Part;
+
+/// Where pinned answers are stored unless another file is given.
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+/// A known-good answer for one part of a day, tied to the input it was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub input_hash: String,
+    pub answer: String,
+}
+
+/// The answers file: one tab-separated `year day part input-hash answer`
+/// entry per line, with `#` starting a comment.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: Vec<Answer>,
+}
+impl Answers {
+    /// Loads the answers file at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("Error in {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Error reading {}", path.display())),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| parse_entry(line).with_context(|| format!("line {}", i + 1)))
+            .collect::<Result<_>>()?;
+        Ok(Answers { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = String::from("# year\tday\tpart\tinput-hash\tanswer\n");
+        for entry in &self.entries {
+            writeln!(
+                content,
+                "{}\t{}\t{}\t{}\t{}",
+                entry.year, entry.day, entry.part, entry.input_hash, entry.answer
+            )?;
+        }
+        fs::write(path, content).with_context(|| format!("Error writing {}", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part, input_hash: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| {
+                (e.year, e.day, e.part, e.input_hash.as_str()) == (year, day, part, input_hash)
+            })
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Records an answer, replacing any existing one for the same year, day,
+    /// part and input.
+    pub fn pin(&mut self, answer: Answer) {
+        self.entries.retain(|e| {
+            (e.year, e.day, e.part, &e.input_hash)
+                != (answer.year, answer.day, answer.part, &answer.input_hash)
+        });
+        self.entries.push(answer);
+        self.entries.sort_by_key(|e| (e.year, e.day, e.part));
+    }
+}
+
+fn parse_entry(line: &str) -> Result<Answer> {
+    let fields = line.splitn(5, '\t').collect::<Vec<_>>();
+    if fields.len() != 5 {
+        return Err(anyhow!("Expected 5 tab-separated fields: {}", line));
+    }
+
+    Ok(Answer {
+        year: fields[0]
+            .parse()
+            .with_context(|| format!("Invalid year: {}", fields[0]))?,
+        day: fields[1]
+            .parse()
+            .with_context(|| format!("Invalid day: {}", fields[1]))?,
+        part: fields[2].parse()?,
+        input_hash: fields[3].to_string(),
+        answer: fields[4].to_string(),
+    })
+}
+
+/// A stable (FNV-1a) hash of an input, so that answers are only compared
+/// against the input they were computed from.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_and_parse() {
+        let mut answers = Answers::default();
+        let hash = input_hash("1\n2\n");
+        answers.pin(Answer {
+            year: 2020,
+            day: 7,
+            part: Part::Two,
+            input_hash: hash.clone(),
+            answer: "old".to_string(),
+        });
+        answers.pin(Answer {
+            year: 2020,
+            day: 7,
+            part: Part::Two,
+            input_hash: hash.clone(),
+            answer: "8030".to_string(),
+        });
+        assert_eq!(answers.entries.len(), 1);
+        assert_eq!(answers.get(2020, 7, Part::Two, &hash), Some("8030"));
+        assert_eq!(answers.get(2020, 7, Part::One, &hash), None);
+        assert_eq!(answers.get(2021, 7, Part::Two, &hash), None);
+
+        let parsed = Answers::parse("# comment\n2020\t7\t2\tabc\t8030\n").unwrap();
+        assert_eq!(parsed.get(2020, 7, Part::Two, "abc"), Some("8030"));
+        assert!(Answers::parse("2020\t7\t3\tabc\t1\n").is_err());
+        assert!(Answers::parse("7\t2\tabc\t8030\n").is_err());
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_ne!(input_hash("a"), input_hash("b"));
+    }
+}
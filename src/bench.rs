@@ -0,0 +1,67 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::input::PuzzleInput;
+use crate::{Part, Result, Solution};
+
+/// Summary of the timings of repeated runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub iterations: usize,
+    pub parse: Stats,
+    pub parts: Vec<(Part, Stats)>,
+}
+
+/// Times parsing and each of `parts` separately over `iterations` runs.
+pub fn bench<S: Solution>(input: &str, parts: &[Part], iterations: usize) -> Result<Bench> {
+    let input = PuzzleInput::new(input);
+    let iterations = iterations.max(1);
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut part_samples = vec![Vec::with_capacity(iterations); parts.len()];
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(black_box(&input))?);
+        parse_samples.push(start.elapsed());
+
+        for (&part, samples) in parts.iter().zip(&mut part_samples) {
+            let start = Instant::now();
+            match part {
+                Part::One => {
+                    black_box(S::part_one(&parsed)?);
+                }
+                Part::Two => {
+                    black_box(S::part_two(&parsed)?);
+                }
+            }
+            samples.push(start.elapsed());
+        }
+    }
+
+    Ok(Bench {
+        iterations,
+        parse: Stats::from_samples(parse_samples),
+        parts: parts
+            .iter()
+            .copied()
+            .zip(part_samples.into_iter().map(Stats::from_samples))
+            .collect(),
+    })
+}
@@ -0,0 +1,842 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+
+use aoc::answers::{self, Answer, Answers};
+use aoc::bench::Stats;
+use aoc::days::y2020::day01::{self, Amount, Combination, Day01};
+use aoc::input::Source;
+use aoc::site::{self, Config};
+use aoc::submissions::{self, Outcome, Submission, Submissions};
+use aoc::{days, generate, json, pool, scaffold, Part, Solver};
+
+const USAGE: &str =
+    "Usage: aoc [--year <year>] run <days> [--part <1|2>] [--input <path>] [--format <text|json>] [--jobs <n>]
+       aoc verify [<days>] [--part <1|2>] [--input <path>] [--answers <path>]
+       aoc pin [<days>] [--part <1|2>] [--input <path>] [--answers <path>]
+       aoc bench [<days>] [--part <1|2>] [--input <path>] [--iterations <n>]
+       aoc fetch <days> [--input <path>]
+       aoc submit <day> <1|2> [--input <path>] [--history <path>]
+       aoc new <day> [--input <path>]
+       aoc generate <day> [--size <n>] [--seed <n>]
+       aoc expenses [--target <n>] [--entries <k> | --subset] [--all | --count] [--input <path>]
+
+run     solves the selected days and prints the answers
+verify  checks the answers against the ones pinned in the answers file
+pin     records the current answers in the answers file
+bench   times parsing and each part over a number of iterations
+fetch   downloads the inputs, skipping ones that already exist and aren't empty
+submit  solves a part and submits the answer
+new     generates the solution module and binary for a day from the templates
+generate  prints a random input for a day, for stress testing
+expenses  finds entries of the 2020 day 1 expense report that sum to a target
+
+<days> is a day number (7), a range (1..=12 or 1..13), a comma-separated
+list of those (1,3,5..=7) or 'all' for every implemented day. verify, pin and
+bench default to all days.
+
+--year selects the event the days are from, defaulting to the latest one with
+any implemented days. It can be given anywhere on the command line.
+
+--input reads the input for a single day from <path>, or stdin if it is '-'.
+Otherwise inputs are read from $AOC_INPUT_DIR/<year>/NN.txt, defaulting to
+input/<year>/NN.txt.
+
+run solves the days in parallel on --jobs threads, defaulting to the number of
+CPUs, and prints a summary table if there are several days.
+
+--format json prints one JSON object per solved part, with the year, day, part,
+answer or error and the time taken in microseconds.
+
+expenses prints the first <k> entries (2 by default) that sum to --target (2020
+by default), and the lines they are on. --all prints every such combination
+and --count only how many there are; combinations that only differ in which of
+several equal entries they use are counted once. --subset prints the fewest
+entries, however many, that sum to the target.
+
+--answers defaults to answers.txt, --iterations to 10, --size to 1000 and
+--seed to 0.
+
+submit records every answer in the --history file, submissions.txt by
+default, and refuses to submit answers that are already known to be wrong or
+while the site is still asking to wait.
+
+fetch and submit read the session token and the base URL of the site from
+aoc.conf (or the file in $AOC_CONFIG), with 'session = <token>' and
+'base_url = <url>' lines. $AOC_SESSION and $AOC_BASE_URL override them.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Verify,
+    Pin,
+    Bench,
+    Fetch,
+    Submit,
+    New,
+    Generate,
+    Expenses,
+}
+impl Command {
+    fn defaults_to_all_days(self) -> bool {
+        matches!(self, Command::Verify | Command::Pin | Command::Bench)
+    }
+}
+
+/// Which combinations of expense report entries to print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Listing {
+    First,
+    All,
+    Count,
+    Subset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct Args {
+    command: Command,
+    year: u16,
+    days: Vec<u8>,
+    parts: Vec<Part>,
+    source: Option<Source>,
+    answers: PathBuf,
+    history: PathBuf,
+    iterations: usize,
+    jobs: usize,
+    size: usize,
+    seed: u64,
+    format: Format,
+    target: Amount,
+    entries: Option<usize>,
+    listing: Listing,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match args.command {
+        Command::Run => run(&args),
+        Command::Verify => verify(&args),
+        Command::Pin => pin(&args),
+        Command::Bench => bench(&args),
+        Command::Fetch => fetch(&args),
+        Command::Submit => submit(&args),
+        Command::New => new(&args),
+        Command::Generate => generate(&args),
+        Command::Expenses => expenses(&args),
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Args> {
+    let mut year = None;
+    let mut args = args.into_iter();
+    let mut command = args.next();
+    while let Some("--year" | "-y") = command.as_deref() {
+        year = Some(parse_year(args.next())?);
+        command = args.next();
+    }
+    let command = match command.as_deref() {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("pin") => Command::Pin,
+        Some("bench") => Command::Bench,
+        Some("fetch") => Command::Fetch,
+        Some("submit") => Command::Submit,
+        Some("new") => Command::New,
+        Some("generate") => Command::Generate,
+        Some("expenses") => Command::Expenses,
+        Some(cmd) => return Err(anyhow!("Unknown command: {}", cmd)),
+        None => return Err(anyhow!("No command given")),
+    };
+
+    let mut days = None;
+    let mut part = None;
+    let mut source = None;
+    let mut answers = PathBuf::from(answers::DEFAULT_PATH);
+    let mut history = PathBuf::from(submissions::DEFAULT_PATH);
+    let mut iterations = 10;
+    let mut jobs = pool::default_threads();
+    let mut size = 1000;
+    let mut seed = 0;
+    let mut format = Format::Text;
+    let mut target = day01::TARGET;
+    let mut entries = None;
+    let mut listing = Listing::First;
+    let mut subset = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" | "-y" => year = Some(parse_year(args.next())?),
+            "--part" | "-p" => {
+                let arg = args.next().context("--part requires an argument")?;
+                part = Some(arg.parse()?);
+            }
+            "--input" | "-i" => {
+                let path = args.next().context("--input requires an argument")?;
+                source = Some(Source::from_arg(&path));
+            }
+            "--answers" if matches!(command, Command::Verify | Command::Pin) => {
+                let path = args.next().context("--answers requires an argument")?;
+                answers = PathBuf::from(path);
+            }
+            "--history" if command == Command::Submit => {
+                let path = args.next().context("--history requires an argument")?;
+                history = PathBuf::from(path);
+            }
+            "--iterations" | "-n" if command == Command::Bench => {
+                let n = args.next().context("--iterations requires an argument")?;
+                iterations = n
+                    .parse()
+                    .with_context(|| format!("Invalid number of iterations: {}", n))?;
+            }
+            "--size" if command == Command::Generate => {
+                let n = args.next().context("--size requires an argument")?;
+                size = n.parse().with_context(|| format!("Invalid size: {}", n))?;
+            }
+            "--seed" if command == Command::Generate => {
+                let n = args.next().context("--seed requires an argument")?;
+                seed = n.parse().with_context(|| format!("Invalid seed: {}", n))?;
+            }
+            "--jobs" | "-j" if command == Command::Run => {
+                let n = args.next().context("--jobs requires an argument")?;
+                jobs = n
+                    .parse()
+                    .with_context(|| format!("Invalid number of jobs: {}", n))?;
+            }
+            "--format" | "-f" if command == Command::Run => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some(f) => return Err(anyhow!("Unknown format: {}", f)),
+                    None => return Err(anyhow!("--format requires an argument")),
+                };
+            }
+            "--target" | "-t" if command == Command::Expenses => {
+                let n = args.next().context("--target requires an argument")?;
+                target = n
+                    .parse()
+                    .with_context(|| format!("Invalid target: {}", n))?;
+            }
+            "--entries" | "-k" if command == Command::Expenses => {
+                let n = args.next().context("--entries requires an argument")?;
+                entries = Some(
+                    n.parse()
+                        .with_context(|| format!("Invalid number of entries: {}", n))?,
+                );
+            }
+            "--all" | "-a" if command == Command::Expenses => listing = Listing::All,
+            "--count" | "-c" if command == Command::Expenses => listing = Listing::Count,
+            "--subset" | "-s" if command == Command::Expenses => subset = true,
+            _ if days.is_none() && command != Command::Expenses => days = Some(arg),
+            _ if command == Command::Submit && part.is_none() => part = Some(arg.parse()?),
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+    if command == Command::Expenses {
+        if year.is_some_and(|year| year != 2020) {
+            return Err(anyhow!("expenses only works on the 2020 expense report"));
+        }
+        year = Some(2020);
+        days = Some("1".to_string());
+    }
+    if subset {
+        if entries.is_some() {
+            return Err(anyhow!("--subset can't be combined with --entries"));
+        }
+        if listing != Listing::First {
+            return Err(anyhow!("--subset can't be combined with --all or --count"));
+        }
+        listing = Listing::Subset;
+    }
+    let year = match year {
+        Some(year) => year,
+        None => *days::years().last().context("No days are implemented")?,
+    };
+    let days = match days {
+        Some(spec) => parse_days(&spec, year)?,
+        None if command.defaults_to_all_days() => days::implemented(year),
+        None => return Err(anyhow!("No days given")),
+    };
+    if source.is_some() && days.len() > 1 {
+        return Err(anyhow!("--input can only be used with a single day"));
+    }
+    if command == Command::Submit && (days.len() > 1 || part.is_none()) {
+        return Err(anyhow!("submit needs a single day and part"));
+    }
+    if matches!(command, Command::New | Command::Generate) && days.len() > 1 {
+        return Err(anyhow!("new and generate need a single day"));
+    }
+    let parts = part.map_or_else(|| Part::ALL.to_vec(), |part| vec![part]);
+
+    Ok(Args {
+        command,
+        year,
+        days,
+        parts,
+        source,
+        answers,
+        history,
+        iterations,
+        jobs,
+        size,
+        seed,
+        format,
+        target,
+        entries,
+        listing,
+    })
+}
+
+fn source(args: &Args, day: u8) -> Source {
+    match &args.source {
+        Some(source) => source.clone(),
+        None => Source::default_for(args.year, day),
+    }
+}
+
+fn load_day(args: &Args, day: u8) -> Result<(&'static dyn Solver, String)> {
+    let solver = days::solver(args.year, day)
+        .with_context(|| format!("Day {} of {} is not implemented", day, args.year))?;
+    let input = source(args, day).read()?;
+    Ok((solver, input))
+}
+
+struct PartResult {
+    part: Part,
+    answer: Result<String, aoc::Error>,
+    duration: Duration,
+}
+
+/// The input of a day and the result for each selected part.
+type SolvedDay = (String, Vec<PartResult>);
+
+/// Reads the input for `day` and solves the selected parts.
+fn solve_day(args: &Args, day: u8) -> Result<SolvedDay> {
+    let (solver, input) = load_day(args, day)?;
+
+    let results = args
+        .parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = solver.solve(&input, part);
+            PartResult {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok((input, results))
+}
+
+/// Returns whether every selected part was solved successfully.
+fn run(args: &Args) -> Result<bool> {
+    let start = Instant::now();
+    let mut solved = pool::map(&args.days, args.jobs, |&day| solve_day(args, day))
+        .into_iter()
+        .zip(&args.days)
+        .map(|(result, &day)| {
+            let result = result.unwrap_or_else(|message| Err(anyhow!("Panicked: {}", message)));
+            (day, result)
+        })
+        .collect::<Vec<_>>();
+    let wall_time = start.elapsed();
+    solved.sort_by_key(|&(day, _)| day);
+
+    if args.format == Format::Text && solved.len() > 1 {
+        return Ok(print_summary(args, &solved, wall_time));
+    }
+
+    let mut success = true;
+    for (day, result) in solved {
+        let (input, results) = match result {
+            Ok(solved) => solved,
+            // Every selected part fails if the day can't be loaded
+            Err(e) if args.format == Format::Json => {
+                for &part in &args.parts {
+                    let error = Err(format!("{:#}", e));
+                    print_json_record(args.year, day, part, error, Duration::ZERO);
+                }
+                success = false;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        success &= results.iter().all(|r| r.answer.is_ok());
+
+        match args.format {
+            Format::Text => print_text(day, &results, &source(args, day), &input),
+            Format::Json => print_json(args.year, day, &results),
+        }
+    }
+
+    Ok(success)
+}
+
+fn print_text(day: u8, results: &[PartResult], source: &Source, input: &str) {
+    println!("Day {}", day);
+    let mut previous: Option<&PartResult> = None;
+    for result in results {
+        match (&result.answer, previous) {
+            (Ok(answer), _) => println!("Part {}: {}", result.part, answer),
+            // Both parts fail the same way if the input doesn't parse
+            (Err(e), Some(previous)) if previous.answer.as_ref() == Err(e) => {
+                println!("Part {}: error: as for part {}", result.part, previous.part)
+            }
+            (Err(e), _) => println!(
+                "Part {}: error: {}",
+                result.part,
+                e.render(&source.to_string(), input)
+            ),
+        }
+        previous = Some(result);
+    }
+}
+
+/// Prints a table of the answers and the time taken for each day, followed by
+/// the details of any errors. Returns whether every selected part was solved.
+fn print_summary(args: &Args, solved: &[(u8, Result<SolvedDay>)], wall_time: Duration) -> bool {
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+        "Time".to_string(),
+    ]];
+    let mut errors = Vec::new();
+    let mut total = Duration::ZERO;
+    for (day, result) in solved {
+        let (input, results) = match result {
+            Ok(solved) => solved,
+            Err(e) => {
+                rows.push(vec![
+                    day.to_string(),
+                    "error".to_string(),
+                    "error".to_string(),
+                ]);
+                errors.push(format!("Day {}: {:#}", day, e));
+                continue;
+            }
+        };
+
+        let mut row = vec![day.to_string()];
+        let mut previous: Option<&PartResult> = None;
+        for part in Part::ALL {
+            let result = results.iter().find(|r| r.part == part);
+            row.push(match result.map(|r| &r.answer) {
+                Some(Ok(answer)) => answer.clone(),
+                Some(Err(e)) => {
+                    // Both parts fail the same way if the input doesn't parse
+                    if previous.is_none_or(|p| p.answer.as_ref() != Err(e)) {
+                        let source = source(args, *day).to_string();
+                        errors.push(format!(
+                            "Day {} part {}: {}",
+                            day,
+                            part,
+                            e.render(&source, input)
+                        ));
+                    }
+                    "error".to_string()
+                }
+                None => "-".to_string(),
+            });
+            previous = result.or(previous);
+        }
+
+        let duration = results.iter().map(|r| r.duration).sum::<Duration>();
+        total += duration;
+        row.push(format!("{:.2?}", duration));
+        rows.push(row);
+    }
+
+    let widths = (0..4)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    for row in &rows {
+        let cell = |i: usize| row.get(i).map_or("", String::as_str);
+        println!(
+            "{:>w0$}  {:<w1$}  {:<w2$}  {:>w3$}",
+            cell(0),
+            cell(1),
+            cell(2),
+            cell(3),
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        );
+    }
+    println!(
+        "{} days in {:.2?} wall time, {:.2?} solving",
+        solved.len(),
+        wall_time,
+        total
+    );
+
+    for error in &errors {
+        println!();
+        println!("{}", error);
+    }
+    errors.is_empty()
+}
+
+fn print_json(year: u16, day: u8, results: &[PartResult]) {
+    for result in results {
+        let answer = result.answer.as_deref().map_err(|e| format!("{:#}", e));
+        print_json_record(year, day, result.part, answer, result.duration);
+    }
+}
+
+fn print_json_record(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: Result<&str, String>,
+    duration: Duration,
+) {
+    let success = answer.is_ok();
+    let (answer, error) = match answer {
+        Ok(answer) => (json::string(answer), "null".to_string()),
+        Err(e) => ("null".to_string(), json::string(&e)),
+    };
+    println!(
+        r#"{{"year":{},"day":{},"part":{},"success":{},"answer":{},"error":{},"duration_us":{}}}"#,
+        year,
+        day,
+        part,
+        success,
+        answer,
+        error,
+        duration.as_micros()
+    );
+}
+
+/// Returns whether every selected part matched its pinned answer. Parts
+/// without a pinned answer for their input are reported but don't fail.
+fn verify(args: &Args) -> Result<bool> {
+    let answers = Answers::load(&args.answers)?;
+
+    let (mut ok, mut failed, mut unpinned) = (0, 0, 0);
+    for &day in &args.days {
+        let (input, results) = match solve_day(args, day) {
+            Ok(solved) => solved,
+            Err(e) => {
+                println!("Day {}: error: {:#}", day, e);
+                failed += args.parts.len();
+                continue;
+            }
+        };
+        let hash = answers::input_hash(&input);
+
+        for PartResult { part, answer, .. } in results {
+            let expected = answers.get(args.year, day, part, &hash);
+            match (answer, expected) {
+                (Ok(answer), Some(expected)) if answer == expected => {
+                    println!("Day {} part {}: ok", day, part);
+                    ok += 1;
+                }
+                (Ok(answer), Some(expected)) => {
+                    println!(
+                        "Day {} part {}: MISMATCH: expected {}, got {}",
+                        day, part, expected, answer
+                    );
+                    failed += 1;
+                }
+                (Ok(answer), None) => {
+                    println!("Day {} part {}: not pinned ({})", day, part, answer);
+                    unpinned += 1;
+                }
+                (Err(e), _) => {
+                    println!("Day {} part {}: error: {:#}", day, part, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} ok, {} failed, {} not pinned", ok, failed, unpinned);
+    Ok(failed == 0)
+}
+
+/// Records the answers of all successfully solved parts.
+fn pin(args: &Args) -> Result<bool> {
+    let mut answers = Answers::load(&args.answers)?;
+
+    let mut success = true;
+    for &day in &args.days {
+        let (input, results) = match solve_day(args, day) {
+            Ok(solved) => solved,
+            Err(e) => {
+                println!("Day {}: error: {:#}", day, e);
+                success = false;
+                continue;
+            }
+        };
+        let input_hash = answers::input_hash(&input);
+
+        for PartResult { part, answer, .. } in results {
+            match answer {
+                Ok(answer) => {
+                    println!("Day {} part {}: pinned {}", day, part, answer);
+                    answers.pin(Answer {
+                        year: args.year,
+                        day,
+                        part,
+                        input_hash: input_hash.clone(),
+                        answer,
+                    });
+                }
+                Err(e) => {
+                    println!("Day {} part {}: error: {:#}", day, part, e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    answers.save(&args.answers)?;
+    Ok(success)
+}
+
+/// Prints the min/median/max timings for parsing and each selected part.
+fn bench(args: &Args) -> Result<bool> {
+    let mut success = true;
+    for &day in &args.days {
+        let bench = match load_day(args, day)
+            .and_then(|(solver, input)| Ok(solver.bench(&input, &args.parts, args.iterations)?))
+        {
+            Ok(bench) => bench,
+            Err(e) => {
+                println!("Day {}: error: {:#}", day, e);
+                success = false;
+                continue;
+            }
+        };
+
+        println!("Day {} ({} iterations)", day, bench.iterations);
+        print_stats("parse", &bench.parse);
+        for (part, stats) in &bench.parts {
+            print_stats(&format!("part {}", part), stats);
+        }
+    }
+
+    Ok(success)
+}
+
+/// Downloads the inputs of the selected days that don't exist yet.
+fn fetch(args: &Args) -> Result<bool> {
+    let config = Config::load()?;
+    for &day in &args.days {
+        let path = match source(args, day) {
+            Source::File(path) => path,
+            Source::Stdin => return Err(anyhow!("Can't fetch an input to stdin")),
+        };
+
+        if site::fetch_input(&config, args.year, day, &path)? {
+            println!("Day {}: downloaded to {}", day, path.display());
+        } else {
+            println!("Day {}: {} already exists", day, path.display());
+        }
+    }
+
+    Ok(true)
+}
+
+/// Solves the selected part and submits the answer, unless the history shows
+/// that it can't be right or that the site isn't accepting answers yet.
+/// Returns whether the answer was correct.
+fn submit(args: &Args) -> Result<bool> {
+    let (day, part) = (args.days[0], args.parts[0]);
+    let (solver, input) = load_day(args, day)?;
+    let answer = solver
+        .solve(&input, part)
+        .map_err(|e| anyhow!("{}", e.render(&source(args, day).to_string(), &input)))?;
+
+    let config = Config::load()?;
+    let mut history = Submissions::load(&args.history)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    if let Err(e) = history.check(args.year, day, part, &answer, now) {
+        println!(
+            "Day {} part {}: not submitting {}: {}",
+            day, part, answer, e
+        );
+        return Ok(false);
+    }
+
+    let (outcome, message, wait) = site::submit_answer(&config, args.year, day, part, &answer)?;
+    println!("Day {} part {}: {} is {}", day, part, answer, outcome);
+    println!("{}", message);
+    history.record(Submission {
+        year: args.year,
+        day,
+        part,
+        time: now,
+        outcome,
+        wait,
+        answer,
+    });
+    history.save(&args.history)?;
+
+    Ok(outcome == Outcome::Correct)
+}
+
+/// Generates the files for a new day, which must be run from the repository root.
+fn new(args: &Args) -> Result<bool> {
+    let day = args.days[0];
+    let input = match source(args, day) {
+        Source::File(path) => path,
+        Source::Stdin => return Err(anyhow!("Can't create an input on stdin")),
+    };
+
+    for path in scaffold::new_day(Path::new("."), args.year, day, &input)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(true)
+}
+
+fn generate(args: &Args) -> Result<bool> {
+    let day = args.days[0];
+    let input = generate::generate(args.year, day, args.size, args.seed)
+        .with_context(|| format!("There is no generator for day {} of {}", day, args.year))?;
+    print!("{}", input);
+    Ok(true)
+}
+
+fn expenses(args: &Args) -> Result<bool> {
+    let report = aoc::load::<Day01>(&source(args, 1))?;
+    let (target, k) = (args.target, args.entries.unwrap_or(2));
+    let mut combinations = report.combinations(target, k);
+
+    match args.listing {
+        Listing::First => {
+            let found = combinations
+                .next()
+                .with_context(|| format!("No {} entries sum to {}", k, target))?;
+            print_combination(&found, target);
+        }
+        Listing::All => combinations.for_each(|found| print_combination(&found, target)),
+        Listing::Count => println!("{}", combinations.count()),
+        Listing::Subset => {
+            let found = report
+                .smallest_subset(target)?
+                .with_context(|| format!("No entries sum to {}", target))?;
+            print_combination(&found, target);
+        }
+    }
+    Ok(true)
+}
+
+fn print_combination(combination: &Combination, target: Amount) {
+    if combination.entries.is_empty() {
+        println!("No entries are needed to sum to {}", target);
+        return;
+    }
+    let terms = combination
+        .entries
+        .iter()
+        .map(Amount::to_string)
+        .collect::<Vec<_>>();
+    let lines = combination
+        .lines
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>();
+    println!(
+        "{} = {} (lines {})",
+        terms.join(" + "),
+        target,
+        lines.join(", ")
+    );
+}
+
+fn print_stats(label: &str, stats: &Stats) {
+    println!(
+        "  {:<8} min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+        label, stats.min, stats.median, stats.max
+    );
+}
+
+fn parse_year(arg: Option<String>) -> Result<u16> {
+    let arg = arg.context("--year requires an argument")?;
+    match arg.parse() {
+        Ok(year) if year >= days::FIRST_YEAR => Ok(year),
+        _ => Err(anyhow!("Invalid year: {}", arg)),
+    }
+}
+
+/// Parses a selection of days into ascending days without repeats, with
+/// 'all' meaning all implemented days of the event in `year`.
+fn parse_days(spec: &str, year: u16) -> Result<Vec<u8>> {
+    if spec == "all" {
+        return Ok(days::implemented(year));
+    }
+
+    let mut days = Vec::new();
+    for item in spec.split(',') {
+        let parse = |s: &str| {
+            s.parse::<u8>()
+                .with_context(|| format!("Invalid day: {}", s))
+        };
+        if let Some((start, end)) = item.split_once("..=") {
+            days.extend(parse(start)?..=parse(end)?);
+        } else if let Some((start, end)) = item.split_once("..") {
+            days.extend(parse(start)?..parse(end)?);
+        } else {
+            days.push(parse(item)?);
+        }
+    }
+    if days.is_empty() {
+        return Err(anyhow!("Empty day range: {}", spec));
+    }
+
+    // Days selected more than once are still only handled once
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7", 2020).unwrap(), vec![7]);
+        assert_eq!(parse_days("1..=3", 2020).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_days("1..3", 2020).unwrap(), vec![1, 2]);
+        assert_eq!(parse_days("1,4..=5", 2020).unwrap(), vec![1, 4, 5]);
+        assert_eq!(parse_days("all", 2020).unwrap(), days::implemented(2020));
+        assert_eq!(parse_days("1,1", 2020).unwrap(), vec![1]);
+        assert_eq!(parse_days("5,1..=3,2", 2020).unwrap(), vec![1, 2, 3, 5]);
+        assert!(parse_days("3..1", 2020).is_err());
+        assert!(parse_days("x", 2020).is_err());
+    }
+}
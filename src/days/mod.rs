@@ -0,0 +1,28 @@
+use crate::Solver;
+
+pub mod y2020;
+
+/// The first year there was an event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Looks up the solver for a day of an event, if that day has been implemented.
+pub fn solver(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    match year {
+        2020 => y2020::solver(day),
+        _ => None,
+    }
+}
+
+/// All days of an event that have a solver, in order.
+pub fn implemented(year: u16) -> Vec<u8> {
+    (1..=25)
+        .filter(|&day| solver(year, day).is_some())
+        .collect()
+}
+
+/// All events that have at least one solver, in order.
+pub fn years() -> Vec<u16> {
+    (FIRST_YEAR..FIRST_YEAR + 100)
+        .filter(|&year| !implemented(year).is_empty())
+        .collect()
+}
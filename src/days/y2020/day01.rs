@@ -0,0 +1,609 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use crate::error;
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+/// An entry of an expense report. Entries can be anything that fits in a
+/// `u64` or an `i64`, so sums of them can't overflow.
+pub type Amount = i128;
+
+/// The sum the entries of the expense report should add up to.
+pub const TARGET: Amount = 2020;
+
+/// The most distinct sums that `Report::smallest_subset` keeps track of, which
+/// for entries that aren't negative allows any target below it.
+pub const MAX_SUBSET_SUMS: usize = 1 << 20;
+
+/// The most bits, one for each entry and sum, that `Report::smallest_subset`
+/// uses to remember which entries reach which sums, which makes 32 MiB.
+pub const MAX_SUBSET_BITS: usize = 1 << 28;
+
+/// The entries of an expense report, sorted, together with the (1-based)
+/// line each one is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    entries: Vec<Amount>,
+    lines: Vec<usize>,
+}
+impl Report {
+    /// Creates a report from the entries in the order of their lines.
+    pub fn new(entries: Vec<Amount>) -> Self {
+        let mut order = (0..entries.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| (entries[i], i));
+        Report {
+            entries: order.iter().map(|&i| entries[i]).collect(),
+            lines: order.iter().map(|&i| i + 1).collect(),
+        }
+    }
+
+    /// The entries in ascending order.
+    pub fn entries(&self) -> &[Amount] {
+        &self.entries
+    }
+
+    /// Every combination of `k` entries that sums to `target`, in ascending
+    /// order of their amounts. Combinations that only differ in which of
+    /// several equal entries they use are only reported once, with the
+    /// earliest lines.
+    pub fn combinations(&self, target: Amount, k: usize) -> impl Iterator<Item = Combination> + '_ {
+        Combinations::new(&self.entries, target, k).map(move |indices| Combination {
+            entries: indices.iter().map(|&i| self.entries[i]).collect(),
+            lines: indices.iter().map(|&i| self.lines[i]).collect(),
+        })
+    }
+
+    /// Finds a subset of the entries with as few entries as possible that
+    /// sums to `target`, however many that are. This goes through the entries
+    /// once, keeping track of the fewest entries each sum can be reached with,
+    /// and remembers for every entry which sums it improved. Time and memory
+    /// are therefore proportional to the number of entries times the number
+    /// of sums that could lead to the target, and it fails if there are more
+    /// than `MAX_SUBSET_SUMS` such sums or `MAX_SUBSET_BITS` bits in total.
+    pub fn smallest_subset(&self, target: Amount) -> Result<Option<Combination>> {
+        let negative = self.entries.iter().filter(|&&e| e < 0).sum::<Amount>();
+        let positive = self.entries.iter().filter(|&&e| e > 0).sum::<Amount>();
+        // Only sums from which the remaining entries can still reach the
+        // target are worth keeping track of
+        let low = negative.max(target.saturating_sub(positive));
+        let high = positive.min(target.saturating_sub(negative));
+        if low > 0 || high < 0 {
+            return Ok(None);
+        }
+        let size = usize::try_from(high - low + 1)
+            .ok()
+            .filter(|&size| size <= MAX_SUBSET_SUMS)
+            .ok_or_else(|| {
+                Error::TooLarge(format!(
+                    "Too many sums to search for {}; at most {} are supported",
+                    target, MAX_SUBSET_SUMS
+                ))
+            })?;
+        if size.saturating_mul(self.entries.len()) > MAX_SUBSET_BITS {
+            return Err(Error::TooLarge(format!(
+                "Searching {} sums for {} entries would take more than {} MiB",
+                size,
+                self.entries.len(),
+                MAX_SUBSET_BITS / 8 / 1024 / 1024
+            )));
+        }
+        let index = |sum: Amount| usize::try_from(sum - low).ok().filter(|&i| i < size);
+
+        // The fewest entries each sum can be reached with, and for each entry
+        // a bit set of the sums whose fewest entries changed when it was added
+        let mut fewest = vec![usize::MAX; size];
+        let mut improved = Vec::with_capacity(self.entries.len());
+        fewest[index(0).unwrap()] = 0;
+        for &entry in &self.entries {
+            let mut sums = vec![0u64; size.div_ceil(64)];
+            let mut add = |from: usize| {
+                let to = match index(low + from as Amount + entry) {
+                    Some(to) if fewest[from] != usize::MAX => to,
+                    _ => return,
+                };
+                if fewest[from] + 1 < fewest[to] {
+                    fewest[to] = fewest[from] + 1;
+                    sums[to / 64] |= 1 << (to % 64);
+                }
+            };
+            // Visit the sums so that none is reached from a sum that already
+            // includes this entry
+            if entry > 0 {
+                (0..size).rev().for_each(&mut add);
+            } else if entry < 0 {
+                (0..size).for_each(&mut add);
+            }
+            improved.push(sums);
+        }
+
+        let mut sum = target;
+        let mut picked = Vec::new();
+        for (i, sums) in improved.iter().enumerate().rev() {
+            if index(sum).is_some_and(|s| sums[s / 64] & 1 << (s % 64) != 0) {
+                picked.push(i);
+                sum -= self.entries[i];
+            }
+        }
+        // Nothing was picked if the target can't be reached at all
+        if sum != 0 {
+            return Ok(None);
+        }
+
+        picked.reverse();
+        Ok(Some(Combination {
+            entries: picked.iter().map(|&i| self.entries[i]).collect(),
+            lines: picked.iter().map(|&i| self.lines[i]).collect(),
+        }))
+    }
+}
+
+/// Entries of a report that sum to a target, and the lines they are on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combination {
+    pub entries: Vec<Amount>,
+    pub lines: Vec<usize>,
+}
+impl Combination {
+    /// Multiplies the entries, failing if the product doesn't fit.
+    pub fn product(&self) -> Result<Amount> {
+        self.entries
+            .iter()
+            .try_fold(1, |product: Amount, &entry| product.checked_mul(entry))
+            .ok_or_else(|| {
+                let terms = self
+                    .entries
+                    .iter()
+                    .map(Amount::to_string)
+                    .collect::<Vec<_>>();
+                Error::Overflow(format!("Product of {} is too large", terms.join(" * ")))
+            })
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Report;
+    type PartOne = Amount;
+    type PartTwo = Amount;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        let entries = error::collect_all(
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| parse_amount(line).map_err(|e| e.at_line(i + 1))),
+        )?;
+        Ok(Report::new(entries))
+    }
+
+    fn part_one(report: &Self::Input) -> Result<Amount> {
+        product_of_sum(report, TARGET, 2)
+    }
+
+    fn part_two(report: &Self::Input) -> Result<Amount> {
+        product_of_sum(report, TARGET, 3)
+    }
+}
+
+/// Parses an entry, which has to fit in either an `i64` or a `u64`.
+fn parse_amount(s: &str) -> Result<Amount> {
+    match s.parse::<i64>() {
+        Ok(amount) => Ok(amount.into()),
+        // Negative entries can't fit in a u64 either, so keep the i64 error
+        Err(e) if s.starts_with('-') => Err(e.into()),
+        Err(_) => Ok(s.parse::<u64>()?.into()),
+    }
+}
+
+/// Multiplies the first `k` entries found that sum to `target`.
+fn product_of_sum(report: &Report, target: Amount, k: usize) -> Result<Amount> {
+    if report.entries.len() < k {
+        return Err(Error::MissingData(format!(
+            "Need {} entries, but there are only {}",
+            k,
+            report.entries.len()
+        )));
+    }
+    report
+        .combinations(target, k)
+        .next()
+        .ok_or_else(|| Error::NoSolution(format!("No {} entries sum to {}", k, target)))?
+        .product()
+}
+
+/// Iterates over the strictly increasing indices of `k` sorted entries that
+/// sum to a target. All but the last two entries are fixed in turn, and those
+/// are found with two pointers, which takes O(n^(k-1)) time. The indices
+/// always refer to the whole of `entries` rather than to a subslice, so every
+/// entry is picked at most once.
+struct Combinations<'a> {
+    entries: &'a [Amount],
+    target: Amount,
+    k: usize,
+    /// The indices of the first `k - 2` entries.
+    prefix: Vec<usize>,
+    /// The two pointers for the last two entries, while searching after a prefix.
+    pair: Option<(usize, usize)>,
+    started: bool,
+}
+impl<'a> Combinations<'a> {
+    fn new(entries: &'a [Amount], target: Amount, k: usize) -> Self {
+        Combinations {
+            entries,
+            target,
+            k,
+            prefix: Vec::new(),
+            pair: None,
+            started: false,
+        }
+    }
+
+    fn prefix_sum(&self) -> Amount {
+        self.prefix.iter().map(|&i| self.entries[i]).sum()
+    }
+
+    /// The index after the run of entries equal to the one at `i`.
+    fn skip_equal(&self, i: usize) -> usize {
+        i + self.entries[i..].partition_point(|&e| e == self.entries[i])
+    }
+
+    /// Whether the prefix can be extended by the entry at `i` without the
+    /// smallest possible sum, using the entries right after it for the rest,
+    /// exceeding the target. If it can't, no later entry can either.
+    fn fits(&self, i: usize) -> bool {
+        let needed = self.k - self.prefix.len();
+        match self.entries.get(i..i + needed) {
+            Some(smallest) => self.prefix_sum() + smallest.iter().sum::<Amount>() <= self.target,
+            None => false,
+        }
+    }
+
+    /// Moves on to the next prefix of `k - 2` entries that doesn't rule out
+    /// reaching the target, returning whether there is one.
+    fn next_prefix(&mut self) -> bool {
+        let mut next = if !self.started {
+            self.started = true;
+            0
+        } else {
+            match self.prefix.pop() {
+                Some(i) => self.skip_equal(i),
+                None => return false,
+            }
+        };
+
+        while self.prefix.len() < self.k - 2 {
+            if self.fits(next) {
+                self.prefix.push(next);
+                next += 1;
+            } else {
+                match self.prefix.pop() {
+                    Some(i) => next = self.skip_equal(i),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// The only result for fewer than two entries, where there is nothing to
+    /// search for with two pointers.
+    fn trivial(&self) -> Option<Vec<usize>> {
+        if self.k == 0 {
+            return (self.target == 0).then(Vec::new);
+        }
+        let i = self.entries.partition_point(|&e| e < self.target);
+        (self.entries.get(i) == Some(&self.target)).then(|| vec![i])
+    }
+}
+impl Iterator for Combinations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.k < 2 {
+            if self.started {
+                return None;
+            }
+            self.started = true;
+            return self.trivial();
+        }
+
+        loop {
+            if let Some((low, high)) = self.pair {
+                // A target too far from the prefix can't be reached by two entries
+                let rest = match self.target.checked_sub(self.prefix_sum()) {
+                    Some(rest) if low < high => rest,
+                    _ => {
+                        self.pair = None;
+                        continue;
+                    }
+                };
+                match (self.entries[low] + self.entries[high]).cmp(&rest) {
+                    Ordering::Less => self.pair = Some((low + 1, high)),
+                    Ordering::Greater => self.pair = Some((low, high - 1)),
+                    Ordering::Equal => {
+                        // Use the first of several equal entries after `low`
+                        let first = low
+                            + 1
+                            + self.entries[low + 1..high]
+                                .partition_point(|&e| e < self.entries[high]);
+                        let next_low = self.skip_equal(low);
+                        let next_high = self.entries[..high]
+                            .iter()
+                            .rposition(|&e| e != self.entries[high])
+                            .unwrap_or(0);
+                        self.pair = Some((next_low, next_high));
+
+                        let mut found = self.prefix.clone();
+                        found.extend([low, first]);
+                        return Some(found);
+                    }
+                }
+                continue;
+            }
+
+            if !self.next_prefix() {
+                return None;
+            }
+            let start = self.prefix.last().map_or(0, |&i| i + 1);
+            match self.entries.len().checked_sub(1) {
+                Some(end) => self.pair = Some((start, end)),
+                None => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use itertools::Itertools;
+
+    use super::*;
+
+    /// Tries every pair in order, without relying on the entries being sorted.
+    fn part_one_naive(lines: &[Amount]) -> Option<Amount> {
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                if lines[i] + lines[j] == 2020 {
+                    return Some(lines[i] * lines[j]);
+                }
+            }
+        }
+        None
+    }
+
+    /// Tries every triple in order, without relying on the entries being sorted.
+    fn part_two_naive(lines: &[Amount]) -> Option<Amount> {
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                for k in j + 1..lines.len() {
+                    if lines[i] + lines[j] + lines[k] == 2020 {
+                        return Some(lines[i] * lines[j] * lines[k]);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Every distinct combination of values, found by trying all of them.
+    fn combinations_naive(lines: &[Amount], target: Amount, k: usize) -> BTreeSet<Vec<Amount>> {
+        (0..lines.len())
+            .combinations(k)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|&i| lines[i])
+                    .sorted()
+                    .collect::<Vec<_>>()
+            })
+            .filter(|values| values.iter().sum::<Amount>() == target)
+            .collect()
+    }
+
+    fn values(report: &Report, target: Amount, k: usize) -> Vec<Vec<Amount>> {
+        report
+            .combinations(target, k)
+            .map(|found| found.entries)
+            .collect()
+    }
+
+    #[test]
+    fn test_combinations() {
+        let report = Day01::parse(&PuzzleInput::new("1721\n979\n366\n299\n675\n1456\n")).unwrap();
+        let first = report.combinations(2020, 2).next().unwrap();
+        assert_eq!(first.entries, vec![299, 1721]);
+        assert_eq!(first.lines, vec![4, 1]);
+        assert_eq!(values(&report, 2020, 3), vec![vec![366, 675, 979]]);
+        assert_eq!(values(&report, 3365, 4), vec![vec![299, 366, 979, 1721]]);
+        assert_eq!(values(&report, 979, 1), vec![vec![979]]);
+        assert_eq!(values(&report, 0, 0), vec![vec![]]);
+        assert!(values(&report, 2020, 7).is_empty());
+        assert_eq!(product_of_sum(&report, 2020, 2), Ok(514579));
+        assert_eq!(product_of_sum(&report, 2020, 3), Ok(241861950));
+
+        let report = Report::new(vec![1, 9, 5, 5, 9, 1, 5, 3, 7]);
+        assert_eq!(
+            values(&report, 10, 2),
+            vec![vec![1, 9], vec![3, 7], vec![5, 5]]
+        );
+        let found = report.combinations(10, 2).collect::<Vec<_>>();
+        assert_eq!(found[0].lines, vec![1, 2]);
+        assert_eq!(found[2].lines, vec![3, 4]);
+        assert_eq!(report.combinations(15, 3).count(), 3);
+    }
+
+    #[test]
+    fn test_wide_entries() {
+        let report = Day01::parse(&PuzzleInput::new(
+            "-9223372036854775808\n18446744073709551615\n-5\n2025\n",
+        ))
+        .unwrap();
+        assert_eq!(Day01::part_one(&report), Ok(-10125));
+        assert_eq!(
+            product_of_sum(&report, u64::MAX as Amount - 5, 2),
+            Ok(-5 * u64::MAX as Amount)
+        );
+        assert_eq!(
+            product_of_sum(&report, i64::MIN as Amount - 5, 2),
+            Ok(-5 * i64::MIN as Amount)
+        );
+        assert!(Day01::parse(&PuzzleInput::new("18446744073709551616\n")).is_err());
+        let e = Day01::parse(&PuzzleInput::new("-9223372036854775809\n")).unwrap_err();
+        assert!(e.to_string().contains("too small"), "{}", e);
+
+        let report = Report::new(vec![u64::MAX.into(); 2]);
+        assert!(matches!(
+            product_of_sum(&report, 2 * u64::MAX as Amount, 2),
+            Err(Error::Overflow(_))
+        ));
+        let report = Report::new(vec![i64::MIN.into(); 2]);
+        assert_eq!(
+            product_of_sum(&report, 2 * i64::MIN as Amount, 2),
+            Ok(1 << 126)
+        );
+    }
+
+    #[test]
+    fn test_smallest_subset() {
+        let report = Report::new(vec![1721, 979, 366, 299, 675, 1456]);
+        let subset = |report: &Report, target| {
+            report
+                .smallest_subset(target)
+                .unwrap()
+                .map(|found| (found.entries, found.lines))
+        };
+        assert_eq!(subset(&report, 2020), Some((vec![299, 1721], vec![4, 1])));
+        assert_eq!(
+            subset(&report, 366 + 675 + 979 + 1456),
+            // Fewer entries reach the same sum
+            Some((vec![299, 1456, 1721], vec![4, 6, 1]))
+        );
+        assert_eq!(subset(&report, 0), Some((vec![], vec![])));
+        assert_eq!(subset(&report, 1), None);
+        assert_eq!(subset(&report, 10_000), None);
+
+        let report = Report::new(vec![-7, 3, 4, 10, -2]);
+        assert_eq!(subset(&report, 1), Some((vec![-2, 3], vec![5, 2])));
+        assert_eq!(subset(&report, -9), Some((vec![-7, -2], vec![1, 5])));
+        assert_eq!(subset(&report, 8), Some((vec![-2, 10], vec![5, 4])));
+
+        let report = Report::new(vec![1, MAX_SUBSET_SUMS as Amount]);
+        assert!(matches!(
+            report.smallest_subset(MAX_SUBSET_SUMS as Amount),
+            Err(Error::TooLarge(_))
+        ));
+        // Few enough sums, but too many of them for each entry
+        let entries = MAX_SUBSET_BITS / MAX_SUBSET_SUMS + 1;
+        let amount = (MAX_SUBSET_SUMS / entries) as Amount;
+        let report = Report::new(vec![amount; entries]);
+        assert!(matches!(
+            report.smallest_subset(amount * entries as Amount),
+            Err(Error::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_few_entries() {
+        let empty = Day01::parse(&PuzzleInput::new("")).unwrap();
+        assert!(matches!(
+            Day01::part_one(&empty),
+            Err(Error::MissingData(_))
+        ));
+        let report = Report::new(vec![1010, 1010]);
+        assert!(matches!(
+            Day01::part_two(&report),
+            Err(Error::MissingData(_))
+        ));
+        assert_eq!(report.combinations(2020, 3).count(), 0);
+        assert_eq!(report.combinations(1010, 1).count(), 1);
+    }
+
+    #[test]
+    fn test_entries_are_not_reused() {
+        let report = |entries: &[Amount]| Report::new(entries.to_vec());
+        // The old triple search could pick the second entry twice here
+        assert!(Day01::part_two(&report(&[286, 867, 868])).is_err());
+        assert!(Day01::part_two(&report(&[20, 1000, 1500])).is_err());
+        assert_eq!(Day01::part_two(&report(&[20, 1000, 1000])), Ok(20_000_000));
+        assert!(Day01::part_one(&report(&[1010])).is_err());
+        assert_eq!(Day01::part_one(&report(&[1010, 1010])), Ok(1_020_100));
+        assert_eq!(values(&report(&[5, 5, 5]), 15, 3), vec![vec![5, 5, 5]]);
+        assert!(values(&report(&[5, 5]), 15, 3).is_empty());
+    }
+
+    fn entries() -> impl Strategy<Value = Vec<Amount>> {
+        prop::collection::vec(0..=2020 as Amount, 0..40).prop_map(|mut lines| {
+            lines.sort_unstable();
+            lines
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn part_one_matches_naive(lines in entries()) {
+            let report = Report::new(lines.clone());
+            prop_assert_eq!(Day01::part_one(&report).ok(), part_one_naive(&lines));
+        }
+
+        #[test]
+        fn part_two_matches_naive(lines in entries()) {
+            let report = Report::new(lines.clone());
+            prop_assert_eq!(Day01::part_two(&report).ok(), part_two_naive(&lines));
+        }
+
+        #[test]
+        fn indices_are_distinct(lines in entries(), target in 0..=4000 as Amount, k in 0..5usize) {
+            for indices in Combinations::new(&lines, target, k) {
+                prop_assert_eq!(indices.len(), k);
+                prop_assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+                prop_assert_eq!(indices.iter().map(|&i| lines[i]).sum::<Amount>(), target);
+            }
+        }
+
+        #[test]
+        fn smallest_subset_is_minimal(
+            lines in prop::collection::vec(-30..30 as Amount, 0..10),
+            target in -100..100 as Amount,
+        ) {
+            let found = Report::new(lines.clone()).smallest_subset(target).unwrap();
+            let fewest = (0..=lines.len())
+                .find(|&k| !combinations_naive(&lines, target, k).is_empty());
+            prop_assert_eq!(found.as_ref().map(|found| found.entries.len()), fewest);
+            if let Some(found) = found {
+                prop_assert_eq!(found.entries.iter().sum::<Amount>(), target);
+                let mut lines_used = found.lines.clone();
+                lines_used.sort_unstable();
+                lines_used.dedup();
+                prop_assert_eq!(lines_used.len(), found.lines.len());
+                for (&line, &entry) in found.lines.iter().zip(&found.entries) {
+                    prop_assert_eq!(lines[line - 1], entry);
+                }
+            }
+        }
+
+        #[test]
+        fn combinations_match_naive(
+            lines in prop::collection::vec(-50..50 as Amount, 0..12),
+            target in -150..150 as Amount,
+            k in 0..5usize,
+        ) {
+            let found = Report::new(lines.clone())
+                .combinations(target, k)
+                .map(|found| {
+                    prop_assert!(found.lines.iter().zip(&found.entries).all(|(&line, &entry)| lines[line - 1] == entry));
+                    Ok(found.entries)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let distinct = found.iter().cloned().collect::<BTreeSet<_>>();
+            prop_assert_eq!(found.len(), distinct.len());
+            prop_assert_eq!(distinct, combinations_naive(&lines, target, k));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, satisfy},
+    sequence::{terminated, tuple},
+};
+
+use crate::error::collect_all;
+use crate::input::PuzzleInput;
+use crate::parsing::{nom_parse, range};
+use crate::{Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+pub struct PwInfo {
+    num1: usize,
+    num2: usize,
+    char: char,
+    pw: String,
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<PwInfo>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        collect_all(
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| parse_line(line).map_err(|e| e.at_line(i + 1))),
+        )
+    }
+
+    fn part_one(passwords: &Self::Input) -> Result<usize> {
+        Ok(passwords.iter().filter(|p| is_valid_pw_part1(p)).count())
+    }
+
+    fn part_two(passwords: &Self::Input) -> Result<usize> {
+        Ok(passwords.iter().filter(|p| is_valid_pw_part2(p)).count())
+    }
+}
+
+fn parse_line(line: &str) -> Result<PwInfo> {
+    let (positions, char, pw) = nom_parse(
+        tuple((
+            terminated(range, tag(" ")),
+            terminated(satisfy(|c| c.is_ascii_lowercase()), tag(": ")),
+            alphanumeric1,
+        )),
+        line,
+    )?;
+
+    Ok(PwInfo {
+        num1: *positions.start(),
+        num2: *positions.end(),
+        char,
+        pw: pw.to_string(),
+    })
+}
+
+fn is_valid_pw_part1(pw_info: &PwInfo) -> bool {
+    let num_chars = pw_info.pw.chars().filter(|&c| c == pw_info.char).count();
+
+    pw_info.num1 <= num_chars && num_chars <= pw_info.num2
+}
+
+fn is_valid_pw_part2(pw_info: &PwInfo) -> bool {
+    // Positions are 1-based, and ones outside of the password never match
+    let matches_at = |pos: usize| {
+        pos.checked_sub(1).and_then(|i| pw_info.pw.chars().nth(i)) == Some(pw_info.char)
+    };
+
+    matches_at(pw_info.num1) ^ matches_at(pw_info.num2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part1(line: &str) -> bool {
+        is_valid_pw_part1(&parse_line(line).unwrap())
+    }
+
+    fn part2(line: &str) -> bool {
+        is_valid_pw_part2(&parse_line(line).unwrap())
+    }
+
+    #[test]
+    fn test_validation_part1_examples() {
+        assert!(part1("1-3 a: abcde"));
+        assert!(!part1("1-3 b: cdefg"));
+        assert!(part1("2-9 c: ccccccccc"));
+    }
+
+    #[test]
+    fn test_validation_part1_complex() {
+        assert!(part1("1-3 a: ababa"));
+        assert!(!part1("1-3 a: abababa"));
+        assert!(part1("1-3 a: abaab"));
+    }
+
+    #[test]
+    fn test_validation_part2_examples() {
+        assert!(part2("1-3 a: abcde"));
+        assert!(!part2("1-3 b: cdefg"));
+        assert!(!part2("2-9 c: ccccccccc"));
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            if let Ok(pw_info) = parse_line(&s) {
+                is_valid_pw_part1(&pw_info);
+                is_valid_pw_part2(&pw_info);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::grid::Grid;
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Grid<char>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        Grid::parse(input, |c| match c {
+            '.' | '#' => Ok(c),
+            _ => Err(Error::parse(format!("Invalid character: {}", c))),
+        })
+    }
+
+    fn part_one(map: &Self::Input) -> Result<usize> {
+        Ok(get_tree_count(map, 3, 1))
+    }
+
+    fn part_two(map: &Self::Input) -> Result<usize> {
+        [[1, 1], [3, 1], [5, 1], [7, 1], [1, 2]]
+            .iter()
+            .map(|&[x, y]| get_tree_count(map, x, y))
+            .try_fold(1, usize::checked_mul)
+            .ok_or_else(|| Error::Overflow("Product of the tree counts is too large".to_string()))
+    }
+}
+
+fn get_tree_count(map: &Grid<char>, step_x: usize, step_y: usize) -> usize {
+    (0..map.height())
+        .step_by(step_y)
+        .zip((0..).step_by(step_x))
+        .filter(|&(y, x)| *map.get_wrapping((x, y)) == '#')
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_overflow() {
+        // Every slope hits a tree on each row it visits
+        let map = Day03::parse(&PuzzleInput::new(&"#\n".repeat(10_000))).unwrap();
+        assert_eq!(Day03::part_one(&map), Ok(10_000));
+        assert!(matches!(Day03::part_two(&map), Err(Error::Overflow(_))));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use nom::{character::complete::space1, combinator::cut, multi::separated_list1};
+use regex::Regex;
+
+use crate::error::collect_all;
+use crate::input::PuzzleInput;
+use crate::parsing::{column_of, key_value, nom_parse};
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+lazy_static! {
+    static ref CM_VALUE: Regex = Regex::new(r"^(?P<val>\d+)cm$").unwrap();
+    static ref IN_VALUE: Regex = Regex::new(r"^(?P<val>\d+)in$").unwrap();
+    static ref HCL: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
+    static ref PID: Regex = Regex::new(r"^[0-9]{9}$").unwrap();
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Passport {
+    byr: Option<String>,
+    iyr: Option<String>,
+    eyr: Option<String>,
+    hgt: Option<String>,
+    hcl: Option<String>,
+    ecl: Option<String>,
+    pid: Option<String>,
+    cid: Option<String>,
+}
+impl Passport {
+    fn is_valid(&self) -> bool {
+        matches!(
+            self,
+            Passport {
+                byr: Some(_),
+                iyr: Some(_),
+                eyr: Some(_),
+                hgt: Some(_),
+                hcl: Some(_),
+                ecl: Some(_),
+                pid: Some(_),
+                cid: _
+            }
+        )
+    }
+
+    fn is_valid_part2(&self) -> bool {
+        fn fields_valid(p: &Passport) -> Result<bool> {
+            let byr = p.byr.as_ref().unwrap().parse::<u16>()?;
+            if !(1920..=2002).contains(&byr) {
+                return Ok(false);
+            }
+
+            let iyr = p.iyr.as_ref().unwrap().parse::<u16>()?;
+            if !(2010..=2020).contains(&iyr) {
+                return Ok(false);
+            }
+
+            let eyr = p.eyr.as_ref().unwrap().parse::<u16>()?;
+            if !(2020..=2030).contains(&eyr) {
+                return Ok(false);
+            }
+
+            let hgt = p.hgt.as_ref().unwrap();
+            if let Some(captures) = CM_VALUE.captures(hgt) {
+                let val = captures.name("val").unwrap().as_str().parse::<u16>()?;
+                if !(150..=193).contains(&val) {
+                    return Ok(false);
+                }
+            } else if let Some(captures) = IN_VALUE.captures(hgt) {
+                let val = captures.name("val").unwrap().as_str().parse::<u16>()?;
+                if !(59..=76).contains(&val) {
+                    return Ok(false);
+                }
+            } else {
+                return Ok(false);
+            }
+
+            if !HCL.is_match(p.hcl.as_ref().unwrap()) {
+                return Ok(false);
+            }
+
+            match p.ecl.as_ref().unwrap().as_ref() {
+                "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth" => {}
+                _ => return Ok(false),
+            }
+
+            if !PID.is_match(p.pid.as_ref().unwrap()) {
+                return Ok(false);
+            }
+
+            Ok(true)
+        }
+        self.is_valid() && fields_valid(self).unwrap_or(false)
+    }
+}
+impl FromStr for Passport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut passport = Passport::default();
+        let mut errors = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let error = |message: String, key: &str| {
+                Error::parse(message)
+                    .at_line(i + 1)
+                    .at_column(column_of(line, key))
+            };
+
+            let fields = match nom_parse(separated_list1(space1, cut(key_value)), line) {
+                Ok(fields) => fields,
+                Err(e) => {
+                    errors.push(e.at_line(i + 1));
+                    continue;
+                }
+            };
+            for (key, value) in fields {
+                let entry = match key {
+                    "byr" => &mut passport.byr,
+                    "iyr" => &mut passport.iyr,
+                    "eyr" => &mut passport.eyr,
+                    "hgt" => &mut passport.hgt,
+                    "hcl" => &mut passport.hcl,
+                    "ecl" => &mut passport.ecl,
+                    "pid" => &mut passport.pid,
+                    "cid" => &mut passport.cid,
+                    _ => {
+                        errors.push(error(format!("Unknown field: {}", key), key));
+                        continue;
+                    }
+                };
+                // A repeated field overrides the earlier one
+                *entry = Some(value.to_owned());
+            }
+        }
+
+        match Error::combine(errors) {
+            Some(e) => Err(e),
+            None => Ok(passport),
+        }
+    }
+}
+
+impl fmt::Display for Passport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields = [
+            ("byr", &self.byr),
+            ("iyr", &self.iyr),
+            ("eyr", &self.eyr),
+            ("hgt", &self.hgt),
+            ("hcl", &self.hcl),
+            ("ecl", &self.ecl),
+            ("pid", &self.pid),
+            ("cid", &self.cid),
+        ];
+        let fields = fields
+            .iter()
+            .filter_map(|(key, value)| Some(format!("{}:{}", key, value.as_ref()?)))
+            .collect::<Vec<_>>();
+        write!(f, "{}", fields.join(" "))
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        collect_all(input.records().map(|record| record.parse::<Passport>()))
+    }
+
+    fn part_one(passports: &Self::Input) -> Result<usize> {
+        Ok(passports.iter().filter(|p| p.is_valid()).count())
+    }
+
+    fn part_two(passports: &Self::Input) -> Result<usize> {
+        Ok(passports.iter().filter(|p| p.is_valid_part2()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> impl Strategy<Value = Option<String>> {
+        prop::option::of("[!-~]{1,10}")
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = s.parse::<Passport>();
+        }
+
+        #[test]
+        fn round_trips(
+            (byr, iyr, eyr, hgt, hcl, ecl, pid, cid) in
+                (field(), field(), field(), field(), field(), field(), field(), field())
+        ) {
+            let passport = Passport { byr, iyr, eyr, hgt, hcl, ecl, pid, cid };
+            // A passport without any fields is an empty record, which isn't parsed
+            prop_assume!(passport != Passport::default());
+            prop_assert_eq!(passport.to_string().parse::<Passport>(), Ok(passport));
+        }
+    }
+
+    #[test]
+    fn test_duplicate_field() {
+        let passport = "byr:1 iyr:2\nbyr:3".parse::<Passport>().unwrap();
+        assert_eq!(passport.byr.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let input = PuzzleInput::new("byr:1 iyr:2 byr:3\n\n\neyr:3\nhgt:4 foo:5\n\npid=1\n");
+        assert_eq!(
+            Day04::parse(&input).unwrap_err().to_string(),
+            "Parse error on line 5, column 7: Unknown field: foo\n\
+             Parse error on line 7, column 4: expected ':', found '='"
+        );
+    }
+}
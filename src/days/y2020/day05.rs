@@ -0,0 +1,129 @@
+use std::fmt;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u8,
+    pub column: u8,
+    pub id: u16,
+}
+impl FromStr for Seat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn lower((min, max): (u8, u8)) -> (u8, u8) {
+            (min, (max + min).div_ceil(2) - 1)
+        }
+        fn higher((min, max): (u8, u8)) -> (u8, u8) {
+            ((max + min).div_ceil(2), max)
+        }
+
+        if s.len() != 10 || !s.is_ascii() {
+            return Err(Error::parse(format!("Expected 10 characters: {}", s)));
+        }
+        let (rows, columns) = s.split_at(7);
+
+        let (row, _) = rows
+            .chars()
+            .enumerate()
+            .try_fold((0, 127), |acc, (i, c)| match c {
+                'F' => Ok(lower(acc)),
+                'B' => Ok(higher(acc)),
+                _ => Err(Error::parse(format!("Invalid row character: {}", c)).at_column(i + 1)),
+            })?;
+
+        let (column, _) = columns
+            .chars()
+            .enumerate()
+            .try_fold((0, 7), |acc, (i, c)| match c {
+                'L' => Ok(lower(acc)),
+                'R' => Ok(higher(acc)),
+                _ => Err(Error::parse(format!("Invalid column character: {}", c))
+                    .at_column(rows.len() + i + 1)),
+            })?;
+
+        Ok(Seat {
+            row,
+            column,
+            id: row as u16 * 8 + column as u16,
+        })
+    }
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for bit in (0..7).rev() {
+            write!(f, "{}", if self.row >> bit & 1 == 1 { 'B' } else { 'F' })?;
+        }
+        for bit in (0..3).rev() {
+            write!(
+                f,
+                "{}",
+                if self.column >> bit & 1 == 1 {
+                    'R'
+                } else {
+                    'L'
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Seat>;
+    type PartOne = u16;
+    type PartTwo = u16;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        let mut seats = input.parse_lines::<Seat>()?;
+
+        seats.sort_by_key(|seat| seat.id);
+        Ok(seats)
+    }
+
+    fn part_one(seats: &Self::Input) -> Result<u16> {
+        seats
+            .last()
+            .map(|seat| seat.id)
+            .ok_or_else(|| Error::MissingData("No seats found".to_string()))
+    }
+
+    fn part_two(seats: &Self::Input) -> Result<u16> {
+        for (a, b) in seats.iter().tuple_windows::<(_, _)>() {
+            if b.id != a.id + 1 {
+                return Ok(a.id + 1);
+            }
+        }
+
+        Err(Error::NoSolution("No free seat found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = s.parse::<Seat>();
+        }
+
+        #[test]
+        fn round_trips(row in 0..128u8, column in 0..8u8) {
+            let seat = Seat { row, column, id: row as u16 * 8 + column as u16 };
+            prop_assert_eq!(seat.to_string().parse::<Seat>(), Ok(seat));
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::input::PuzzleInput;
+use crate::{Result, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Vec<HashSet<char>>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        Ok(input
+            .records()
+            .map(|record| {
+                record
+                    .text
+                    .lines()
+                    .map(|line| line.chars().filter(|c| c.is_alphabetic()).collect())
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn part_one(groups: &Self::Input) -> Result<usize> {
+        Ok(groups
+            .iter()
+            .map(|group| group.iter().flatten().unique().count())
+            .sum())
+    }
+
+    fn part_two(groups: &Self::Input) -> Result<usize> {
+        Ok(groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .cloned()
+                    .reduce(|x, y| x.intersection(&y).cloned().collect::<HashSet<_>>())
+                    .map_or(0, |answers| answers.len())
+            })
+            .sum())
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char, space1},
+    combinator::{opt, recognize},
+    error::VerboseError,
+    multi::separated_list1,
+    sequence::{separated_pair, terminated, tuple},
+};
+
+use crate::error::collect_all;
+use crate::input::PuzzleInput;
+use crate::parsing::{nom_parse, unsigned, NomResult};
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContainedBag {
+    count: u32,
+    colour: String,
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    /// Map of "outer bag -> inner bags with count"
+    type Input = HashMap<String, Vec<ContainedBag>>;
+    type PartOne = usize;
+    type PartTwo = u32;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        collect_all(
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| nom_parse(bag_line, line).map_err(|e| e.at_line(i + 1))),
+        )
+    }
+
+    fn part_one(outer_to_inner: &Self::Input) -> Result<usize> {
+        // invert map to get map of "inner bag -> outer bags" with just the colour
+        let inner_to_outer = outer_to_inner
+            .iter()
+            .flat_map(|(outer, inner)| inner.iter().map(move |bag| (bag, outer)))
+            .fold(HashMap::new(), |mut map, (inner, outer)| {
+                map.entry(inner.colour.as_str())
+                    .or_insert_with(Vec::new)
+                    .push(outer.as_str());
+                map
+            });
+
+        let containing_gold = get_containing_bags(&inner_to_outer, "shiny gold");
+        Ok(containing_gold.len())
+    }
+
+    fn part_two(outer_to_inner: &Self::Input) -> Result<u32> {
+        get_contained_bag_count(outer_to_inner, "shiny gold")
+    }
+}
+
+/// All bags that eventually contain `bag`, found with an explicit stack so
+/// that long chains of rules can't overflow the call stack.
+fn get_containing_bags<'a>(
+    all_bags: &'a HashMap<&str, Vec<&str>>,
+    bag: &'a str,
+) -> HashSet<&'a str> {
+    let mut candidates = HashSet::new();
+    let mut stack = vec![bag];
+    while let Some(bag) = stack.pop() {
+        for &outer_bag in all_bags.get(bag).into_iter().flatten() {
+            // Only descend into new bags so that cyclic rules terminate
+            if candidates.insert(outer_bag) {
+                stack.push(outer_bag);
+            }
+        }
+    }
+    candidates
+}
+
+/// Counts the bags inside `bag`, depth-first with an explicit stack so that
+/// long chains of rules can't overflow the call stack. Each bag is counted
+/// only once, however many bags it is in.
+fn get_contained_bag_count<'a>(
+    all_bags: &'a HashMap<String, Vec<ContainedBag>>,
+    bag: &'a str,
+) -> Result<u32> {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    // The bags on the stack, which are all inside each other
+    let mut outer_bags = HashSet::new();
+    let mut stack = vec![bag];
+    while let Some(&bag) = stack.last() {
+        let inner_bags = all_bags
+            .get(bag)
+            .ok_or_else(|| Error::MissingData(format!("No rule for {} bags", bag)))?;
+        outer_bags.insert(bag);
+
+        let uncounted = inner_bags
+            .iter()
+            .find(|inner_bag| !counts.contains_key(inner_bag.colour.as_str()));
+        if let Some(inner_bag) = uncounted {
+            if outer_bags.contains(inner_bag.colour.as_str()) {
+                return Err(Error::NoSolution(format!(
+                    "{} bags contain themselves",
+                    inner_bag.colour
+                )));
+            }
+            stack.push(&inner_bag.colour);
+            continue;
+        }
+
+        let mut sum: u32 = 0;
+        for inner_bag in inner_bags {
+            let count = counts[inner_bag.colour.as_str()]
+                .checked_add(1)
+                .and_then(|c| c.checked_mul(inner_bag.count))
+                .and_then(|c| c.checked_add(sum));
+            sum = count.ok_or_else(|| Error::Overflow(format!("Too many bags in {} bags", bag)))?;
+        }
+        counts.insert(bag, sum);
+        outer_bags.remove(bag);
+        stack.pop();
+    }
+    Ok(counts[bag])
+}
+
+fn bag_line(input: &str) -> NomResult<&str, (String, Vec<ContainedBag>)> {
+    terminated(
+        separated_pair(bag, tag(" contain "), contained_bags),
+        char('.'),
+    )(input)
+    .map(|(next, (outer, inner))| (next, (outer.to_string(), inner)))
+}
+
+fn contained_bags(input: &str) -> NomResult<&str, Vec<ContainedBag>> {
+    match tag::<_, _, VerboseError<_>>("no other bags")(input) {
+        Ok((next, _)) => Ok((next, Vec::new())),
+        Err(_) => separated_list1(tag(", "), bag_with_count)(input),
+    }
+}
+
+fn bag_with_count(input: &str) -> NomResult<&str, ContainedBag> {
+    separated_pair(unsigned, space1, bag)(input).map(|(next, (count, colour))| {
+        (
+            next,
+            ContainedBag {
+                count,
+                colour: colour.to_string(),
+            },
+        )
+    })
+}
+
+fn bag(input: &str) -> NomResult<&str, &str> {
+    terminated(
+        recognize(separated_pair(alpha1, space1, alpha1)),
+        tuple((tag(" bag"), opt(char('s')))),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(outer: &str, inner: &[ContainedBag]) -> String {
+        let contents = if inner.is_empty() {
+            "no other bags".to_string()
+        } else {
+            inner
+                .iter()
+                .map(|bag| {
+                    let plural = if bag.count == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", bag.count, bag.colour, plural)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!("{} bags contain {}.", outer, contents)
+    }
+
+    const COLOUR: &str = "[a-z]{1,8} [a-z]{1,8}";
+
+    /// Rules for a chain of `depth` bags, each containing two of the next,
+    /// with the shiny gold bag in the middle.
+    fn chain(depth: usize) -> String {
+        let name = |i: usize| {
+            if i == depth / 2 {
+                return "shiny gold".to_string();
+            }
+            // Colours only have letters, so spell the digits with them
+            let letters: String = i
+                .to_string()
+                .bytes()
+                .map(|d| (d - b'0' + b'a') as char)
+                .collect();
+            format!("dull {}", letters)
+        };
+        (0..depth)
+            .map(|i| match i + 1 {
+                next if next < depth => {
+                    format!("{} bags contain 2 {} bags.\n", name(i), name(next))
+                }
+                _ => format!("{} bags contain no other bags.\n", name(i)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_long_chains() {
+        let bags = Day07::parse(&PuzzleInput::new(&chain(100_000))).unwrap();
+        assert_eq!(Day07::part_one(&bags), Ok(50_000));
+        assert!(matches!(Day07::part_two(&bags), Err(Error::Overflow(_))));
+
+        let bags = Day07::parse(&PuzzleInput::new(&chain(20))).unwrap();
+        // 2 + 4 + ... + 2^9 bags inside the tenth one
+        assert_eq!(Day07::part_two(&bags), Ok(1022));
+    }
+
+    #[test]
+    fn test_cycles() {
+        let input = "shiny gold bags contain 1 dull red bag.\n\
+                     dull red bags contain 2 dark blue bags, 1 shiny gold bag.\n\
+                     dark blue bags contain no other bags.\n";
+        let bags = Day07::parse(&PuzzleInput::new(input)).unwrap();
+        assert_eq!(Day07::part_one(&bags), Ok(2));
+        assert!(matches!(Day07::part_two(&bags), Err(Error::NoSolution(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = nom_parse(bag_line, &s);
+        }
+
+        #[test]
+        fn round_trips(
+            outer in COLOUR,
+            inner in prop::collection::vec((any::<u32>(), COLOUR), 0..5),
+        ) {
+            let inner = inner
+                .into_iter()
+                .map(|(count, colour)| ContainedBag { count, colour })
+                .collect::<Vec<_>>();
+            let line = render(&outer, &inner);
+            prop_assert_eq!(nom_parse(bag_line, &line), Ok((outer, inner)));
+        }
+    }
+}
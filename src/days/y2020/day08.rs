@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{alpha1, char},
+    sequence::separated_pair,
+};
+
+use crate::input::PuzzleInput;
+use crate::parsing::{nom_parse, signed};
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Nop(i16),
+    Acc(i16),
+    Jmp(i16),
+}
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (opcode, operand) = nom_parse(separated_pair(alpha1, char(' '), signed::<i16>), s)?;
+
+        let instruction = match opcode {
+            "nop" => Instruction::Nop(operand),
+            "acc" => Instruction::Acc(operand),
+            "jmp" => Instruction::Jmp(operand),
+            _ => {
+                let message = format!("Unknown instruction: {}", opcode);
+                return Err(Error::parse(message).at_column(1));
+            }
+        };
+        Ok(instruction)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Nop(operand) => write!(f, "nop {:+}", operand),
+            Instruction::Acc(operand) => write!(f, "acc {:+}", operand),
+            Instruction::Jmp(operand) => write!(f, "jmp {:+}", operand),
+        }
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Instruction>;
+    type PartOne = i16;
+    type PartTwo = i16;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        input.parse_lines()
+    }
+
+    fn part_one(instructions: &Self::Input) -> Result<i16> {
+        match run_instructions(instructions)? {
+            (true, _) => Err(Error::NoSolution(
+                "Program finished without looping".to_string(),
+            )),
+            (false, acc) => Ok(acc),
+        }
+    }
+
+    fn part_two(instructions: &Self::Input) -> Result<i16> {
+        for i in 0..instructions.len() {
+            let mut cur_instructions = instructions.clone();
+            if let Some(instruction) = cur_instructions.get_mut(i) {
+                match instruction {
+                    Instruction::Nop(op) => *instruction = Instruction::Jmp(*op),
+                    Instruction::Jmp(op) => *instruction = Instruction::Nop(*op),
+                    _ => continue,
+                }
+            }
+
+            match run_instructions(&cur_instructions) {
+                Ok((true, acc)) => return Ok(acc),
+                // Changes that jump out of the program aren't the fix either
+                Ok((false, _)) | Err(Error::NoSolution(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(Error::NoSolution(
+            "No terminating program found".to_string(),
+        ))
+    }
+}
+
+fn run_instructions(instructions: &[Instruction]) -> Result<(bool, i16)> {
+    let mut ip: usize = 0;
+    let mut acc: i16 = 0;
+    let mut seen = HashSet::new();
+
+    while !seen.contains(&ip) {
+        seen.insert(ip);
+
+        if ip >= instructions.len() {
+            return Ok((true, acc));
+        }
+
+        let next_ip = match instructions[ip] {
+            Instruction::Nop(_) => ip.checked_add(1),
+            Instruction::Acc(op) => {
+                acc = acc
+                    .checked_add(op)
+                    .ok_or_else(|| Error::Overflow("Accumulator out of range".to_string()))?;
+                ip.checked_add(1)
+            }
+            Instruction::Jmp(op) => ip.checked_add_signed(op as isize),
+        };
+        ip = next_ip.ok_or_else(|| {
+            Error::NoSolution(format!("Jump out of the program at instruction {}", ip))
+        })?;
+    }
+
+    Ok((false, acc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            any::<i16>().prop_map(Instruction::Nop),
+            any::<i16>().prop_map(Instruction::Acc),
+            any::<i16>().prop_map(Instruction::Jmp),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = s.parse::<Instruction>();
+        }
+
+        #[test]
+        fn round_trips(instruction in instruction()) {
+            prop_assert_eq!(instruction.to_string().parse::<Instruction>(), Ok(instruction));
+        }
+    }
+}
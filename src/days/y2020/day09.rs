@@ -0,0 +1,101 @@
+use itertools::Itertools;
+
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        input.parse_lines()
+    }
+
+    fn part_one(numbers: &Self::Input) -> Result<usize> {
+        part1(numbers)
+    }
+
+    fn part_two(numbers: &Self::Input) -> Result<usize> {
+        let invalid_num = part1(numbers)?;
+        part2(numbers, invalid_num)
+    }
+}
+
+fn part1(numbers: &[usize]) -> Result<usize> {
+    for (i, &num) in numbers.iter().enumerate().skip(25) {
+        let valid = &numbers[i - 25..i]
+            .iter()
+            .combinations(2)
+            .any(|c| c[0].checked_add(*c[1]) == Some(num));
+
+        if !valid {
+            return Ok(num);
+        }
+    }
+
+    Err(Error::NoSolution("No invalid number found".to_string()))
+}
+
+fn part2(numbers: &[usize], invalid_num: usize) -> Result<usize> {
+    let mut lower = 0;
+    let mut total: usize = 0;
+
+    for upper in 0..numbers.len() {
+        total = total
+            .checked_add(numbers[upper])
+            .ok_or_else(|| Error::Overflow("Sum of range too large".to_string()))?;
+        while total > invalid_num {
+            total -= numbers[lower];
+            lower += 1;
+        }
+
+        // The range has to consist of at least two numbers
+        if total == invalid_num && upper > lower {
+            let slice = &numbers[lower..=upper];
+            let (min, max) = (slice.iter().min().unwrap(), slice.iter().max().unwrap());
+            return min
+                .checked_add(*max)
+                .ok_or_else(|| Error::Overflow("Weakness too large".to_string()));
+        }
+    }
+
+    Err(Error::NoSolution(format!(
+        "No range sums to {}",
+        invalid_num
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums every range of at least two numbers, ordered by where they end and
+    /// then by where they start, which is the range `part2` finds first.
+    fn part2_naive(numbers: &[usize], invalid_num: usize) -> Option<usize> {
+        for upper in 0..numbers.len() {
+            for lower in 0..upper {
+                let slice = &numbers[lower..=upper];
+                if slice.iter().sum::<usize>() == invalid_num {
+                    return Some(slice.iter().min().unwrap() + slice.iter().max().unwrap());
+                }
+            }
+        }
+        None
+    }
+
+    proptest! {
+        #[test]
+        fn part2_matches_naive(
+            numbers in prop::collection::vec(0..1000usize, 0..60),
+            invalid_num in 0..5000usize,
+        ) {
+            prop_assert_eq!(part2(&numbers, invalid_num).ok(), part2_naive(&numbers, invalid_num));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use itertools::Itertools;
+
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    /// Sorted adapter joltages, including the outlet and the device
+    type Input = Vec<u32>;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        let mut adapters = input.parse_lines::<u32>()?;
+        adapters.sort_unstable();
+
+        adapters.insert(0, 0);
+        let device = adapters
+            .last()
+            .unwrap()
+            .checked_add(3)
+            .ok_or_else(|| Error::Overflow("Device joltage too high".to_string()))?;
+        adapters.push(device);
+        Ok(adapters)
+    }
+
+    fn part_one(adapters: &Self::Input) -> Result<usize> {
+        let (ones, threes): (Vec<_>, Vec<_>) = adapters
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| b - a)
+            .partition(|&i| i == 1);
+        Ok(ones.len() * threes.len())
+    }
+
+    fn part_two(adapters: &Self::Input) -> Result<u64> {
+        count_combinations(adapters)
+    }
+}
+
+/// Counts the ways to get from the outlet to the device, going backwards from
+/// the device through the sorted adapters and adding up the ways from each
+/// adapter that is in reach.
+fn count_combinations(adapters: &[u32]) -> Result<u64> {
+    let mut ways = vec![0u64; adapters.len()];
+    for i in (0..adapters.len()).rev() {
+        if i + 1 == adapters.len() {
+            ways[i] = 1;
+            continue;
+        }
+        for j in (i + 1..adapters.len()).take_while(|&j| adapters[j] - adapters[i] <= 3) {
+            ways[i] = ways[i]
+                .checked_add(ways[j])
+                .ok_or_else(|| Error::Overflow("Too many combinations".to_string()))?;
+        }
+    }
+    Ok(ways.first().copied().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the arrangements by checking every subset of the adapters
+    /// between the outlet and the device.
+    fn count_combinations_naive(adapters: &[u32]) -> u64 {
+        let last = adapters.len() - 1;
+        (0..1u32 << adapters.len().saturating_sub(2))
+            .filter(|mask| {
+                adapters
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i == 0 || i == last || mask >> (i - 1) & 1 == 1)
+                    .tuple_windows()
+                    .all(|((_, a), (_, b))| b - a <= 3)
+            })
+            .count() as u64
+    }
+
+    /// The outlet, adapters and device, each one to three jolts apart.
+    fn chain() -> impl Strategy<Value = Vec<u32>> {
+        prop::collection::vec(1..=3u32, 1..16).prop_map(|steps| {
+            steps
+                .iter()
+                .scan(0, |joltage, step| {
+                    *joltage += step;
+                    Some(*joltage)
+                })
+                .fold(vec![0], |mut chain, joltage| {
+                    chain.push(joltage);
+                    chain
+                })
+        })
+    }
+
+    #[test]
+    fn test_long_chains() {
+        let input = (1..=100_000)
+            .map(|i| format!("{}\n", 3 * i))
+            .collect::<String>();
+        let adapters = Day10::parse(&PuzzleInput::new(&input)).unwrap();
+        assert_eq!(Day10::part_two(&adapters), Ok(1));
+
+        let input = (1..=1000).map(|i| format!("{}\n", i)).collect::<String>();
+        let adapters = Day10::parse(&PuzzleInput::new(&input)).unwrap();
+        assert!(matches!(
+            Day10::part_two(&adapters),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn count_combinations_matches_naive(adapters in chain()) {
+            let count = count_combinations(&adapters).unwrap();
+            prop_assert_eq!(count, count_combinations_naive(&adapters));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::grid::{Grid, Point, DIRECTIONS_8};
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tile {
+    Floor,
+    Empty,
+    Occupied,
+}
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '.' => Ok(Tile::Floor),
+            'L' => Ok(Tile::Empty),
+            '#' => Ok(Tile::Occupied),
+            _ => Err(Error::parse(format!("Unrecognized character: {}", c))),
+        }
+    }
+}
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Tile::Floor => '.',
+            Tile::Empty => 'L',
+            Tile::Occupied => '#',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<Tile>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        Grid::parse(input, Tile::try_from)
+    }
+
+    fn part_one(grid: &Self::Input) -> Result<usize> {
+        compute_grid(grid, near_part1, 3)
+    }
+
+    fn part_two(grid: &Self::Input) -> Result<usize> {
+        compute_grid(grid, near_part2, 4)
+    }
+}
+
+/// Applies the seating rules until nothing changes. Since seats only react to
+/// the seats they can see, which see them in turn, the seats either settle
+/// or keep switching between two layouts.
+fn compute_grid<F>(grid: &Grid<Tile>, near_func: F, max_occupied: usize) -> Result<usize>
+where
+    F: Fn(&Grid<Tile>, Point) -> usize,
+{
+    let mut grid = grid.to_owned();
+    let mut previous = None;
+
+    loop {
+        let mut changed = false;
+        let mut new_grid = grid.clone();
+        for point in grid.points() {
+            // Don't compute the visible seats for floor tiles
+            let num_occupied = match grid[point] {
+                Tile::Floor => 0,
+                _ => near_func(&grid, point),
+            };
+
+            new_grid[point] = match grid[point] {
+                Tile::Empty if num_occupied == 0 => {
+                    changed = true;
+                    Tile::Occupied
+                }
+                Tile::Occupied if num_occupied > max_occupied => {
+                    changed = true;
+                    Tile::Empty
+                }
+                _ => grid[point],
+            };
+        }
+        if !changed {
+            break;
+        }
+        if previous.as_ref() == Some(&new_grid) {
+            return Err(Error::NoSolution("The seats never settle".to_string()));
+        }
+        previous = Some(std::mem::replace(&mut grid, new_grid));
+    }
+
+    Ok(grid
+        .cells()
+        .filter(|tile| matches!(tile, Tile::Occupied))
+        .count())
+}
+
+fn near_part1(grid: &Grid<Tile>, point: Point) -> usize {
+    grid.neighbours8(point)
+        .filter(|&p| matches!(grid[p], Tile::Occupied))
+        .count()
+}
+
+fn near_part2(grid: &Grid<Tile>, point: Point) -> usize {
+    DIRECTIONS_8
+        .iter()
+        .filter(|&&dir| occupied_seat_visible(grid, point, dir))
+        .count()
+}
+
+fn occupied_seat_visible(grid: &Grid<Tile>, point: Point, dir: (isize, isize)) -> bool {
+    for p in grid.ray(point, dir) {
+        match grid[p] {
+            Tile::Floor => continue,
+            Tile::Empty => return false,
+            Tile::Occupied => return true,
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never_settles() {
+        // Every seat is next to at least four others, so they all empty and
+        // fill up again together
+        let input = PuzzleInput::new(".LL.\nLLLL\nLLLL\n.LL.\n");
+        let grid = Day11::parse(&input).unwrap();
+        assert!(matches!(Day11::part_one(&grid), Err(Error::NoSolution(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = Day11::parse(&PuzzleInput::new(&s));
+        }
+
+        #[test]
+        fn doesnt_crash_on_chars(c in any::<char>()) {
+            let _ = Tile::try_from(c);
+        }
+
+        #[test]
+        fn round_trips(tile in prop_oneof![Just(Tile::Floor), Just(Tile::Empty), Just(Tile::Occupied)]) {
+            let c = tile.to_string().chars().next().unwrap();
+            prop_assert_eq!(Tile::try_from(c).ok(), Some(tile));
+        }
+    }
+}
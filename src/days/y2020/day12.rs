@@ -0,0 +1,226 @@
+use std::fmt;
+use std::str::FromStr;
+
+use nom::{character::complete::one_of, error::context, sequence::pair};
+
+use crate::input::PuzzleInput;
+use crate::parsing::{nom_parse, signed};
+use crate::{Error, Result, Solution};
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    North(i32),
+    South(i32),
+    East(i32),
+    West(i32),
+    Left(i32),
+    Right(i32),
+    Forward(i32),
+}
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (action, arg) = nom_parse(
+            pair(context("one of NSEWLRF", one_of("NSEWLRF")), signed::<i32>),
+            s,
+        )?;
+        let instruction = match action {
+            'N' => Instruction::North(arg),
+            'S' => Instruction::South(arg),
+            'E' => Instruction::East(arg),
+            'W' => Instruction::West(arg),
+            'L' => Instruction::Left(arg),
+            'R' => Instruction::Right(arg),
+            'F' => Instruction::Forward(arg),
+            _ => {
+                let message = format!("Unrecognized instruction: {}", action);
+                return Err(Error::parse(message).at_column(1));
+            }
+        };
+        Ok(instruction)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (action, arg) = match self {
+            Instruction::North(arg) => ('N', arg),
+            Instruction::South(arg) => ('S', arg),
+            Instruction::East(arg) => ('E', arg),
+            Instruction::West(arg) => ('W', arg),
+            Instruction::Left(arg) => ('L', arg),
+            Instruction::Right(arg) => ('R', arg),
+            Instruction::Forward(arg) => ('F', arg),
+        };
+        write!(f, "{}{}", action, arg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+impl Position {
+    fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    fn translate(&mut self, dx: i32, dy: i32) -> Result<()> {
+        self.x = checked(self.x.checked_add(dx))?;
+        self.y = checked(self.y.checked_add(dy))?;
+        Ok(())
+    }
+
+    fn rotate(&mut self, degrees: i32) -> Result<()> {
+        let (neg_x, neg_y) = (negated(self.x)?, negated(self.y)?);
+        *self = match degrees {
+            90 | -270 => Position::new(self.y, neg_x),
+            180 | -180 => Position::new(neg_x, neg_y),
+            270 | -90 => Position::new(neg_y, self.x),
+            _ => return Err(Error::parse(format!("Unsupported rotation: {}", degrees))),
+        };
+
+        Ok(())
+    }
+
+    fn manhattan_distance(&self) -> Result<i32> {
+        checked(
+            self.x
+                .checked_abs()
+                .zip(self.y.checked_abs())
+                .and_then(|(x, y)| x.checked_add(y)),
+        )
+    }
+}
+
+fn checked(value: Option<i32>) -> Result<i32> {
+    value.ok_or_else(|| Error::Overflow("Position out of range".to_string()))
+}
+
+fn negated(value: i32) -> Result<i32> {
+    checked(value.checked_neg())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Facing {
+    North,
+    South,
+    East,
+    West,
+}
+impl Facing {
+    fn turn(&mut self, degrees: i32) -> Result<()> {
+        let degrees = match degrees {
+            -90 => 270,
+            -180 => 180,
+            -270 => 90,
+            d @ (90 | 180 | 270) => d,
+            _ => return Err(Error::parse(format!("Unsupported turn: {}", degrees))),
+        };
+        let facings = [Self::North, Self::East, Self::South, Self::West];
+        let self_index = facings.iter().position(|f| f == self).unwrap() as i32;
+        let new_index = (self_index + degrees / 90) % facings.len() as i32;
+
+        *self = facings[new_index as usize];
+        Ok(())
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        input.parse_lines()
+    }
+
+    fn part_one(instructions: &Self::Input) -> Result<i32> {
+        part1(instructions)
+    }
+
+    fn part_two(instructions: &Self::Input) -> Result<i32> {
+        part2(instructions)
+    }
+}
+
+fn part1(instructions: &[Instruction]) -> Result<i32> {
+    let mut position = Position::new(0, 0);
+    let mut facing = Facing::East;
+    for instruction in instructions {
+        match *instruction {
+            Instruction::North(a) => position.translate(0, a)?,
+            Instruction::South(a) => position.translate(0, negated(a)?)?,
+            Instruction::East(a) => position.translate(a, 0)?,
+            Instruction::West(a) => position.translate(negated(a)?, 0)?,
+            Instruction::Left(a) => facing.turn(a.wrapping_neg())?,
+            Instruction::Right(a) => facing.turn(a)?,
+            Instruction::Forward(a) => match facing {
+                Facing::North => position.translate(0, a)?,
+                Facing::South => position.translate(0, negated(a)?)?,
+                Facing::East => position.translate(a, 0)?,
+                Facing::West => position.translate(negated(a)?, 0)?,
+            },
+        }
+    }
+
+    position.manhattan_distance()
+}
+
+fn part2(instructions: &[Instruction]) -> Result<i32> {
+    let mut ship = Position::new(0, 0);
+    let mut waypoint = Position::new(10, 1);
+
+    for instruction in instructions {
+        match *instruction {
+            Instruction::North(a) => waypoint.translate(0, a)?,
+            Instruction::South(a) => waypoint.translate(0, negated(a)?)?,
+            Instruction::East(a) => waypoint.translate(a, 0)?,
+            Instruction::West(a) => waypoint.translate(negated(a)?, 0)?,
+            Instruction::Left(a) => waypoint.rotate(a.wrapping_neg())?,
+            Instruction::Right(a) => waypoint.rotate(a)?,
+            Instruction::Forward(a) => ship.translate(
+                checked(a.checked_mul(waypoint.x))?,
+                checked(a.checked_mul(waypoint.y))?,
+            )?,
+        }
+    }
+
+    ship.manhattan_distance()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            any::<i32>().prop_map(Instruction::North),
+            any::<i32>().prop_map(Instruction::South),
+            any::<i32>().prop_map(Instruction::East),
+            any::<i32>().prop_map(Instruction::West),
+            any::<i32>().prop_map(Instruction::Left),
+            any::<i32>().prop_map(Instruction::Right),
+            any::<i32>().prop_map(Instruction::Forward),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(s in r"\PC*") {
+            let _ = s.parse::<Instruction>();
+        }
+
+        #[test]
+        fn round_trips(instruction in instruction()) {
+            prop_assert_eq!(instruction.to_string().parse::<Instruction>(), Ok(instruction));
+        }
+    }
+}
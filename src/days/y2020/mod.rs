@@ -0,0 +1,34 @@
+use crate::Solver;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+/// Looks up the solver for a day of the 2020 event, if that day has been implemented.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    let solver: &dyn Solver = match day {
+        1 => &day01::Day01,
+        2 => &day02::Day02,
+        3 => &day03::Day03,
+        4 => &day04::Day04,
+        5 => &day05::Day05,
+        6 => &day06::Day06,
+        7 => &day07::Day07,
+        8 => &day08::Day08,
+        9 => &day09::Day09,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        12 => &day12::Day12,
+        _ => return None,
+    };
+    Some(solver)
+}
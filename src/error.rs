@@ -0,0 +1,217 @@
+use std::fmt::{self, Write as _};
+use std::iter::FromIterator;
+use std::num::ParseIntError;
+
+use crate::input::PuzzleInput;
+
+/// Everything that can go wrong while solving a day, so that a batch run can
+/// report failures instead of crashing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input is malformed, with one diagnostic for every problem found.
+    Parse(Vec<Diagnostic>),
+    /// Something the solution relies on is missing from the input.
+    MissingData(String),
+    /// The input is well-formed but has no answer.
+    NoSolution(String),
+    /// An intermediate value doesn't fit into its integer type.
+    Overflow(String),
+    /// The input is beyond the limits the solution sets on its memory use.
+    TooLarge(String),
+}
+
+/// A single problem in the input, at a (1-based) line and column if known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+impl Diagnostic {
+    fn location(&self) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!(" on line {}, column {}", line, column),
+            (Some(line), None) => format!(" on line {}", line),
+            (None, Some(column)) => format!(" at column {}", column),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl Error {
+    pub fn parse(message: impl Into<String>) -> Self {
+        Error::Parse(vec![Diagnostic {
+            line: None,
+            column: None,
+            message: message.into(),
+        }])
+    }
+
+    /// Attaches a line number to the diagnostics of a parse error that don't
+    /// have one yet; other errors are returned unchanged.
+    pub fn at_line(self, line: usize) -> Self {
+        self.map_diagnostics(|d| d.line = d.line.or(Some(line)))
+    }
+
+    /// Attaches a column to the diagnostics of a parse error that don't have
+    /// one yet; other errors are returned unchanged.
+    pub fn at_column(self, column: usize) -> Self {
+        self.map_diagnostics(|d| d.column = d.column.or(Some(column)))
+    }
+
+    /// Turns the line numbers of a parse error in a record starting at `line`
+    /// into ones relative to the whole input.
+    pub fn relative_to(self, line: usize) -> Self {
+        self.map_diagnostics(|d| d.line = Some(line + d.line.map_or(0, |offset| offset - 1)))
+    }
+
+    fn map_diagnostics<F: FnMut(&mut Diagnostic)>(self, f: F) -> Self {
+        match self {
+            Error::Parse(mut diagnostics) => {
+                diagnostics.iter_mut().for_each(f);
+                Error::Parse(diagnostics)
+            }
+            e => e,
+        }
+    }
+
+    /// Combines parse errors into a single one with all their diagnostics. If
+    /// there are other errors the first of those is returned instead, as
+    /// parsing can't be reported as merely malformed then.
+    pub fn combine(errors: impl IntoIterator<Item = Error>) -> Option<Error> {
+        let mut diagnostics = Vec::new();
+        for error in errors {
+            match error {
+                Error::Parse(d) => diagnostics.extend(d),
+                e => return Some(e),
+            }
+        }
+        (!diagnostics.is_empty()).then_some(Error::Parse(diagnostics))
+    }
+
+    /// Formats the error for the user, showing the offending line of `input`
+    /// for each parse diagnostic with a caret under the column, or the whole
+    /// line underlined if the column isn't known. `source` names the input,
+    /// usually its path.
+    pub fn render(&self, source: &str, input: &str) -> String {
+        let diagnostics = match self {
+            Error::Parse(diagnostics) => diagnostics,
+            e => return e.to_string(),
+        };
+        let input = PuzzleInput::new(input);
+        let lines = input.lines().collect::<Vec<_>>();
+
+        let mut out = String::new();
+        for (i, d) in diagnostics.iter().enumerate() {
+            if i > 0 {
+                out.push_str("\n\n");
+            }
+            write!(out, "Parse error: {}\n  --> {}", d.message, source).unwrap();
+            let (line, text) = match d
+                .line
+                .and_then(|l| Some((l, *lines.get(l.checked_sub(1)?)?)))
+            {
+                Some(line) => line,
+                None => continue,
+            };
+            write!(out, ":{}", line).unwrap();
+            if let Some(column) = d.column {
+                write!(out, ":{}", column).unwrap();
+            }
+
+            let (indent, underline) = match d.column {
+                Some(column) => (column - 1, 1),
+                None => (0, text.chars().count().max(1)),
+            };
+            let gutter = " ".repeat(line.to_string().len());
+            write!(
+                out,
+                "\n{} |\n{} | {}\n{} | {}{}",
+                gutter,
+                line,
+                text,
+                gutter,
+                " ".repeat(indent),
+                "^".repeat(underline)
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(diagnostics) => {
+                for (i, d) in diagnostics.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "Parse error{}: {}", d.location(), d.message)?;
+                }
+                Ok(())
+            }
+            Error::MissingData(message) => write!(f, "Missing data: {}", message),
+            Error::NoSolution(message) => write!(f, "No solution: {}", message),
+            Error::Overflow(message) => write!(f, "Arithmetic overflow: {}", message),
+            Error::TooLarge(message) => write!(f, "Too large: {}", message),
+        }
+    }
+}
+impl std::error::Error for Error {}
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::parse(e.to_string())
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Collects `results`, gathering the diagnostics of every parse error instead
+/// of stopping at the first one.
+pub fn collect_all<T, C>(results: impl IntoIterator<Item = Result<T>>) -> Result<C>
+where
+    C: FromIterator<T>,
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    match Error::combine(errors) {
+        Some(e) => Err(e),
+        None => Ok(values.into_iter().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_and_render() {
+        let results = ["1", "x", "3", "", "9999999999"]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.parse::<u32>().map_err(|e| Error::from(e).at_line(i + 1)));
+        let error = collect_all::<_, Vec<_>>(results).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 2: invalid digit found in string\n\
+             Parse error on line 4: cannot parse integer from empty string\n\
+             Parse error on line 5: number too large to fit in target type"
+        );
+
+        let error = Error::parse("expected digit, found 'x'")
+            .at_column(3)
+            .relative_to(2);
+        assert_eq!(
+            error.render("in.txt", "abc\n1-x a\n"),
+            "Parse error: expected digit, found 'x'\n  --> in.txt:2:3\n  |\n2 | 1-x a\n  |   ^"
+        );
+    }
+}
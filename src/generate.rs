@@ -0,0 +1,451 @@
+//! Seeded generators of valid puzzle inputs of arbitrary size, for finding the
+//! scaling limits of the solutions. `size` is the number of entries of the
+//! input, such as lines, records or rules, or the side length of a grid.
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+const ADJECTIVES: [&str; 12] = [
+    "bright", "clear", "dark", "dim", "drab", "dull", "faded", "light", "muted", "pale", "plaid",
+    "vibrant",
+];
+
+/// A small SplitMix64 random number generator, which is plenty for test inputs.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, with a negligible bias for small ranges.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let span = range.end() - range.start();
+        match span.checked_add(1) {
+            Some(len) => range.start() + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index below `len`, which must not be 0.
+    pub fn below(&mut self, len: usize) -> usize {
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// True with a probability of `percent`%.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0..=99) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generates an input for `day` of the event in `year`, if there is a
+/// generator for it.
+pub fn generate(year: u16, day: u8, size: usize, seed: u64) -> Option<String> {
+    let generator = match (year, day) {
+        (2020, 1) => day01,
+        (2020, 2) => day02,
+        (2020, 3) => day03,
+        (2020, 4) => day04,
+        (2020, 5) => day05,
+        (2020, 6) => day06,
+        (2020, 7) => day07,
+        (2020, 8) => day08,
+        (2020, 9) => day09,
+        (2020, 10) => day10,
+        (2020, 11) => day11,
+        (2020, 12) => day12,
+        _ => return None,
+    };
+    let mut lines = generator(&mut Rng::new(seed), size);
+    lines.push(String::new());
+    Some(lines.join("\n"))
+}
+
+/// Expense entries with at least one pair and one triple summing to 2020.
+fn day01(rng: &mut Rng, size: usize) -> Vec<String> {
+    let a = rng.range(1..=2019);
+    let b = rng.range(1..=2018);
+    let c = rng.range(1..=2019 - b);
+    let mut entries = vec![a, 2020 - a, b, c, 2020 - b - c];
+    while entries.len() < size {
+        entries.push(rng.range(1..=2019));
+    }
+    rng.shuffle(&mut entries);
+    entries.iter().map(u64::to_string).collect()
+}
+
+/// Password policies and passwords.
+fn day02(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let min = rng.range(1..=10);
+            let max = rng.range(min..=20);
+            let c = letter(rng, 5);
+            let len = rng.range(1..=25);
+            let pw = (0..len).map(|_| letter(rng, 5)).collect::<String>();
+            format!("{}-{} {}: {}", min, max, c, pw)
+        })
+        .collect()
+}
+
+/// A map of trees, 31 columns wide like the real ones.
+fn day03(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| {
+            (0..31)
+                .map(|_| if rng.chance(25) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Passports with missing fields and invalid values thrown in.
+fn day04(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let mut fields = Vec::new();
+        for key in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+            if !rng.chance(90) {
+                continue;
+            }
+            let value = match key {
+                "byr" => rng.range(1900..=2010).to_string(),
+                "iyr" => rng.range(2005..=2025).to_string(),
+                "eyr" => rng.range(2015..=2035).to_string(),
+                "hgt" if rng.chance(50) => format!("{}cm", rng.range(140..=200)),
+                "hgt" => format!("{}in", rng.range(55..=80)),
+                "hcl" => format!("#{:06x}", rng.range(0..=0xff_ffff)),
+                "ecl" => rng
+                    .choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth", "xry"])
+                    .to_string(),
+                "pid" => format!("{:09}", rng.range(0..=999_999_999)),
+                _ => rng.range(1..=999).to_string(),
+            };
+            fields.push(format!("{}:{}", key, value));
+        }
+        rng.shuffle(&mut fields);
+
+        let mut line = String::new();
+        for field in fields {
+            if !line.is_empty() && rng.chance(30) {
+                lines.push(line);
+                line = String::new();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&field);
+        }
+        lines.push(line);
+        lines.push(String::new());
+    }
+    lines.pop();
+    lines
+}
+
+/// Boarding passes for consecutive seats with one free seat in between. As
+/// there are only 1024 seats, `size` is limited to 1023.
+fn day05(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.clamp(3, 1023) as u64;
+    let first = rng.range(0..=1024 - size - 1);
+    let free = rng.range(first + 1..=first + size - 1);
+    let mut seats = (first..=first + size)
+        .filter(|&id| id != free)
+        .map(|id| {
+            let row = (0..7)
+                .rev()
+                .map(|bit| if id >> (bit + 3) & 1 == 1 { 'B' } else { 'F' });
+            let column = (0..3)
+                .rev()
+                .map(|bit| if id >> bit & 1 == 1 { 'R' } else { 'L' });
+            row.chain(column).collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut seats);
+    seats
+}
+
+/// Groups of people's answers.
+fn day06(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        for _ in 0..rng.range(1..=5) {
+            let answers = ('a'..='z').filter(|_| rng.chance(30)).collect::<String>();
+            lines.push(if answers.is_empty() {
+                "a".to_string()
+            } else {
+                answers
+            });
+        }
+        lines.push(String::new());
+    }
+    lines.pop();
+    lines
+}
+
+/// Bag rules forming a hierarchy as deep as there are rules, with the shiny
+/// gold bag in the middle of it. Bags are filled from the bottom up, so that
+/// the number of bags inside each one can be kept within a `u32`.
+fn day07(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(2);
+    let gold = size / 2;
+    let name = |i: usize| {
+        if i == gold {
+            "shiny gold".to_string()
+        } else {
+            format!(
+                "{} {}",
+                ADJECTIVES[i % ADJECTIVES.len()],
+                word(i / ADJECTIVES.len())
+            )
+        }
+    };
+
+    // The limit leaves room for adding one bag on each level
+    let limit = u64::from(u32::MAX) - size as u64;
+    let mut totals = vec![0; size];
+    let mut rules = (0..size)
+        .rev()
+        .map(|i| {
+            // Bags only contain ones further down, so there are no cycles
+            let mut inner = HashSet::new();
+            if i + 1 < size {
+                inner.insert(i + 1);
+                for _ in 0..rng.range(0..=2) {
+                    inner.insert(rng.range(i as u64 + 1..=size as u64 - 1) as usize);
+                }
+            }
+            let mut inner = inner.into_iter().collect::<Vec<_>>();
+            inner.sort_unstable();
+
+            let mut contents = Vec::new();
+            for j in inner {
+                let count = rng.range(1..=3);
+                let total = totals[i] + count * (totals[j] + 1);
+                if j == i + 1 && total > limit {
+                    totals[i] += totals[j] + 1;
+                    contents.push(format!("1 {} bag", name(j)));
+                } else if total <= limit {
+                    totals[i] = total;
+                    let plural = if count == 1 { "" } else { "s" };
+                    contents.push(format!("{} {} bag{}", count, name(j), plural));
+                }
+            }
+
+            let contents = if contents.is_empty() {
+                "no other bags".to_string()
+            } else {
+                contents.join(", ")
+            };
+            format!("{} bags contain {}.", name(i), contents)
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut rules);
+    rules
+}
+
+/// A program that loops because of a single backwards jump, which is reached
+/// as all jumps before it stay in front of it.
+fn day08(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(2);
+    let back = rng.range(size as u64 / 2..=size as u64 - 1) as usize;
+    (0..size)
+        .map(|i| {
+            let limit = if i < back { back } else { size };
+            if i == back {
+                format!("jmp -{}", rng.range(1..=i as u64))
+            } else if i + 1 < limit && rng.chance(20) {
+                format!("jmp +{}", rng.range(1..=(limit - i).min(20) as u64))
+            } else if rng.chance(50) {
+                let arg = rng.range(0..=100) as i64 - 50;
+                format!("acc {:+}", arg)
+            } else {
+                let arg = rng.range(0..=2 * size as u64) as i64 - size as i64;
+                format!("nop {:+}", arg)
+            }
+        })
+        .collect()
+}
+
+/// XMAS data with a preamble of 25 and one invalid number that is the sum of
+/// a range of earlier ones. Valid numbers are sums of earlier ones and so grow
+/// exponentially, which limits `size` to where they still fit into 64 bits.
+fn day09(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut numbers = (0..25).map(|_| rng.range(1..=50)).collect::<Vec<_>>();
+    numbers.sort_unstable();
+    numbers.dedup();
+    while numbers.len() < 25 {
+        numbers.push(50 + numbers.len() as u64);
+    }
+    // Early enough to come before the numbers get too large
+    let mut invalid_at = Some(25 + rng.below(size.saturating_sub(25).clamp(1, 500)));
+
+    while numbers.len() < size.max(26) || invalid_at.is_some() {
+        let window = &numbers[numbers.len() - 25..];
+        if invalid_at.is_some_and(|at| at <= numbers.len()) {
+            // Ranges ending at the last numbers mostly sum to a pair in the
+            // window, so look for one starting further back if needed
+            let sums = pair_sums(window);
+            let range_sum = (0..rng.below(numbers.len() - 1) + 1)
+                .rev()
+                .find_map(|start| {
+                    numbers[start..]
+                        .iter()
+                        .scan(0, |sum, n| {
+                            *sum += n;
+                            Some(*sum)
+                        })
+                        .skip(1)
+                        .find(|sum| !sums.contains(sum))
+                });
+            if let Some(sum) = range_sum {
+                numbers.push(sum);
+                invalid_at = None;
+                continue;
+            }
+        }
+
+        // Summing small numbers keeps the growth as slow as possible
+        let mut smallest = window.to_vec();
+        smallest.sort_unstable();
+        let a = smallest[rng.below(3)];
+        let b = smallest[3 + rng.below(3)];
+        match a.checked_add(b).filter(|&n| n < u64::MAX / 4) {
+            Some(n) => numbers.push(n),
+            None => break,
+        }
+    }
+    numbers.iter().map(u64::to_string).collect()
+}
+
+fn pair_sums(numbers: &[u64]) -> HashSet<u64> {
+    let mut sums = HashSet::new();
+    for (i, a) in numbers.iter().enumerate() {
+        for b in &numbers[i + 1..] {
+            sums.insert(a + b);
+        }
+    }
+    sums
+}
+
+/// A chain of adapters in runs one jolt apart, separated by gaps of three
+/// jolts like the real inputs. A run of `n` steps can be arranged in
+/// `ARRANGEMENTS[n]` ways; runs are kept short once the arrangements of the
+/// whole chain would no longer fit in a `u64`.
+fn day10(rng: &mut Rng, size: usize) -> Vec<String> {
+    const ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+    let mut arrangements: u64 = 1;
+    let mut joltage = 0;
+    let mut adapters = Vec::new();
+    while adapters.len() < size.max(1) {
+        let mut run = rng.range(0..=4) as usize;
+        match arrangements.checked_mul(ARRANGEMENTS[run]) {
+            Some(product) => arrangements = product,
+            None => run = 1,
+        }
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        joltage += 3;
+        adapters.push(joltage);
+    }
+    adapters.truncate(size.max(1));
+    rng.shuffle(&mut adapters);
+    adapters.iter().map(u64::to_string).collect()
+}
+
+/// A square seat layout. Denser layouts than half seats soon contain groups
+/// of seats that keep emptying and filling up forever.
+fn day11(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| if rng.chance(50) { 'L' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Navigation instructions.
+fn day12(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F', 'F']);
+            let arg = match action {
+                'L' | 'R' => 90 * rng.range(1..=3),
+                _ => rng.range(1..=100),
+            };
+            format!("{}{}", action, arg)
+        })
+        .collect()
+}
+
+/// One of the first `n` lowercase letters.
+fn letter(rng: &mut Rng, n: u64) -> char {
+    (b'a' + rng.range(0..=n - 1) as u8) as char
+}
+
+/// A unique word for every `n`.
+fn word(mut n: usize) -> String {
+    let mut word = String::new();
+    loop {
+        word.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    word + "ish"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::Part;
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for year in days::years() {
+            for day in days::implemented(year) {
+                if generate(year, day, 0, 0).is_none() {
+                    continue;
+                }
+                let solver = days::solver(year, day).unwrap();
+                // The large size is beyond what fits in a u8
+                let small = (0..20).map(|seed| (30, seed));
+                for (size, seed) in small.chain(Some((256, 0))) {
+                    let input = generate(year, day, size, seed).unwrap();
+                    assert_eq!(generate(year, day, size, seed).unwrap(), input);
+                    for part in Part::ALL {
+                        if let Err(e) = solver.solve(&input, part) {
+                            panic!(
+                                "{} day {} part {} size {} seed {}: {}\n{}",
+                                year, day, part, size, seed, e, input
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::collect_all;
+use crate::input::PuzzleInput;
+use crate::{Error, Result};
+
+/// A position in a grid as `(x, y)`, with `(0, 0)` in the top left corner.
+pub type Point = (usize, usize);
+
+/// The four orthogonal directions as `(dx, dy)`.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The four orthogonal and four diagonal directions as `(dx, dy)`.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    /// Parses a grid with one row per line, turning characters into cells with
+    /// `cell`. Errors in all rows are reported, but not ones in the cells of
+    /// rows with the wrong width.
+    pub fn parse<F>(input: &PuzzleInput, mut cell: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let rows = collect_all::<_, Vec<Vec<T>>>(input.lines().enumerate().map(|(i, line)| {
+            let row_width = line.chars().count();
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                let message = format!("Expected {} columns, found {}", expected, row_width);
+                return Err(Error::parse(message)
+                    .at_line(i + 1)
+                    .at_column(expected.min(row_width) + 1));
+            }
+
+            collect_all(
+                line.chars()
+                    .enumerate()
+                    .map(|(j, c)| cell(c).map_err(|e| e.at_line(i + 1).at_column(j + 1))),
+            )
+        }))?;
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height: rows.len(),
+                cells: rows.into_iter().flatten().collect(),
+            }),
+            _ => Err(Error::MissingData("Empty grid".to_string())),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Gets a cell as if the grid was repeated infinitely in both directions.
+    pub fn get_wrapping(&self, (x, y): Point) -> &T {
+        &self[(x % self.width, y % self.height)]
+    }
+
+    /// All positions in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The position one step from `point` in direction `(dx, dy)`, if it is in the grid.
+    pub fn step(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The orthogonally adjacent positions that are in the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_4
+            .iter()
+            .filter_map(move |&dir| self.step(point, dir))
+    }
+
+    /// The orthogonally and diagonally adjacent positions that are in the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_8
+            .iter()
+            .filter_map(move |&dir| self.step(point, dir))
+    }
+
+    /// The positions from `point` (exclusive) in direction `dir` up to the edge of the grid.
+    /// There are none for the direction `(0, 0)`, which would never reach the edge.
+    pub fn ray(&self, point: Point, dir: (isize, isize)) -> impl Iterator<Item = Point> + '_ {
+        let mut current = point;
+        std::iter::from_fn(move || {
+            if dir == (0, 0) {
+                return None;
+            }
+            current = self.step(current, dir)?;
+            Some(current)
+        })
+    }
+}
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<char> {
+        Grid::parse(&PuzzleInput::new(s), Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let g = grid("ab\ncd\nef\n");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g[(1, 2)], 'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(*g.get_wrapping((3, 4)), 'd');
+        assert_eq!(g.to_string(), "ab\ncd\nef\n");
+
+        let ragged = Grid::parse(&PuzzleInput::new("ab\nc\nd"), Ok).unwrap_err();
+        assert_eq!(
+            ragged.to_string(),
+            "Parse error on line 2, column 2: Expected 2 columns, found 1\n\
+             Parse error on line 3, column 2: Expected 2 columns, found 1"
+        );
+        let empty = Grid::parse(&PuzzleInput::new(""), Ok);
+        assert!(matches!(empty, Err(Error::MissingData(_))));
+    }
+
+    #[test]
+    fn test_neighbours_and_rays() {
+        let g = grid("abc\ndef\nghi");
+        let chars = |points: Vec<Point>| points.into_iter().map(|p| g[p]).collect::<String>();
+
+        assert_eq!(chars(g.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(chars(g.neighbours8((1, 1)).collect()), "abcdfghi");
+        assert_eq!(chars(g.ray((0, 0), (1, 1)).collect()), "ei");
+        assert_eq!(chars(g.ray((2, 1), (-1, 0)).collect()), "ed");
+        assert_eq!(g.ray((0, 0), (-1, 0)).count(), 0);
+        assert_eq!(g.ray((1, 1), (0, 0)).count(), 0);
+    }
+}
@@ -0,0 +1,293 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+const USER_AGENT: &str = "github.com/majutsushi/aoc-2020";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// A request carrying the session cookie, with an optional form-encoded body.
+/// Plain `http://` URLs are handled directly, which is enough for a local
+/// stand-in server; `https://` ones go through `curl` as there is no TLS here.
+pub fn request(url: &str, session: &str, form: Option<&[(&str, &str)]>) -> Result<Response> {
+    let body = form.map(|fields| {
+        fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+            .collect::<Vec<_>>()
+            .join("&")
+    });
+
+    if let Some(rest) = url.strip_prefix("http://") {
+        send(rest, session, body.as_deref()).with_context(|| format!("Error requesting {}", url))
+    } else if url.starts_with("https://") {
+        curl(url, session, body.as_deref()).with_context(|| format!("Error requesting {}", url))
+    } else {
+        Err(anyhow!("Unsupported URL: {}", url))
+    }
+}
+
+fn send(url: &str, session: &str, body: Option<&str>) -> Result<Response> {
+    let (host, path) = match url.find('/') {
+        Some(i) => url.split_at(i),
+        None => (url, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = TcpStream::connect(&address)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+        if body.is_some() { "POST" } else { "GET" },
+        path,
+        host,
+        USER_AGENT,
+        session
+    );
+    if let Some(body) = body {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+    stream.write_all(request.as_bytes())?;
+
+    read_response(BufReader::new(stream))
+}
+
+fn read_response<R: BufRead>(mut reader: R) -> Result<Response> {
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .with_context(|| format!("Invalid status line: {}", status_line.trim_end()))?;
+
+    let mut chunked = false;
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            } else if name.eq_ignore_ascii_case("content-length") {
+                length = Some(value.parse::<u64>().context("Invalid Content-Length")?);
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = size.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size, 16)
+                .with_context(|| format!("Invalid chunk size: {}", size))?;
+            if size == 0 {
+                break;
+            }
+            let mut chunk = vec![0; size + 2];
+            reader.read_exact(&mut chunk)?;
+            body.extend_from_slice(&chunk[..size]);
+        }
+    } else if let Some(length) = length {
+        reader.take(length).read_to_end(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    Ok(Response {
+        status,
+        body: String::from_utf8(body).context("Response is not valid UTF-8")?,
+    })
+}
+
+fn curl(url: &str, session: &str, body: Option<&str>) -> Result<Response> {
+    // Everything secret is passed as a config file on stdin rather than as
+    // arguments, which other users could see
+    let mut config = format!(
+        "url = {}\nuser-agent = {}\ncookie = {}\nwrite-out = \"\\n%{{http_code}}\"\n",
+        quote(url),
+        quote(USER_AGENT),
+        quote(&format!("session={}", session))
+    );
+    if let Some(body) = body {
+        config.push_str(&format!("data = {}\n", quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--max-time",
+            "30",
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Error running curl, which is needed for https URLs")?;
+    child
+        .stdin
+        .take()
+        .context("Error writing to curl")?
+        .write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let output = String::from_utf8(output.stdout).context("Response is not valid UTF-8")?;
+    let (body, status) = output.rsplit_once('\n').unwrap_or(("", &output));
+    Ok(Response {
+        status: status
+            .parse()
+            .with_context(|| format!("Invalid status from curl: {}", status))?,
+        body: body.to_string(),
+    })
+}
+
+/// Quotes a value for a curl config file, so that it can't end the value early.
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Percent-encodes a form field.
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// A stand-in for the site on a local port, which answers as many requests as
+/// there are `responses` with them in turn. Returns its base URL and a handle
+/// that yields the requests it received.
+#[cfg(test)]
+pub(crate) fn serve(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let length = request
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |length| length.parse().unwrap());
+            let mut body_in = vec![0; length];
+            reader.read_exact(&mut body_in).unwrap();
+            request.push_str(&String::from_utf8(body_in).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (url, server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let mut body = vec![0; 24];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n1\n2\r\n2\r\n\n3\r\n0\r\n\r\n")
+                .unwrap();
+            request
+        });
+
+        let url = format!("http://{}/2020/day/1/answer", address);
+        let response = request(&url, "abc", Some(&[("level", "1"), ("answer", "a b&c")])).unwrap();
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "1\n2\n3".to_string()
+            }
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2020/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("session=a\"b\\c\nd"), "\"session=a\\\"b\\\\c\\nd\"");
+    }
+}
@@ -0,0 +1,171 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::{FromStr, Lines};
+
+use anyhow::{Context, Result};
+
+use crate::error::{self, Error};
+
+/// Environment variable overriding the directory the default inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+impl Source {
+    /// Parses a command line argument, with `-` meaning stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// The input file for `day` of the event in `year`, in that year's
+    /// subdirectory of the input directory.
+    pub fn default_for(year: u16, day: u8) -> Self {
+        let dir = env::var_os(INPUT_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("input"));
+        Source::File(dir.join(year.to_string()).join(format!("{:02}.txt", day)))
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Error reading input from stdin")?;
+                Ok(input)
+            }
+            Source::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("Error reading input file {}", path.display())),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Puzzle input with a leading BOM removed, line endings normalised to `\n`
+/// and trailing whitespace stripped from every line and from the end of the
+/// input, so that hand-edited files parse the same as downloaded ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleInput {
+    text: String,
+}
+impl PuzzleInput {
+    pub fn new(raw: &str) -> Self {
+        let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let text = raw
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        PuzzleInput {
+            text: text.trim_end().to_string(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> Lines<'_> {
+        self.text.lines()
+    }
+
+    /// The records of the input, separated by one or more blank lines.
+    pub fn records(&self) -> impl Iterator<Item = Record<'_>> {
+        let mut line = 1;
+        self.text.split("\n\n").filter_map(move |chunk| {
+            let start = line;
+            line += chunk.matches('\n').count() + 2;
+
+            let leading = chunk.len() - chunk.trim_start_matches('\n').len();
+            let text = chunk.trim_matches('\n');
+            (!text.is_empty()).then(|| Record {
+                line: start + leading,
+                text,
+            })
+        })
+    }
+
+    /// Parses every line, attaching the line number to parse errors and
+    /// reporting the errors of all lines together.
+    pub fn parse_lines<T>(&self) -> crate::Result<Vec<T>>
+    where
+        T: FromStr,
+        Error: From<T::Err>,
+    {
+        error::collect_all(
+            self.lines()
+                .enumerate()
+                .map(|(i, line)| line.parse::<T>().map_err(|e| Error::from(e).at_line(i + 1))),
+        )
+    }
+}
+
+/// A blank-line separated record and the (1-based) line it starts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub line: usize,
+    pub text: &'a str,
+}
+impl Record<'_> {
+    /// Parses the record, turning line numbers in parse errors, which are
+    /// relative to the record, into ones relative to the whole input.
+    pub fn parse<T>(&self) -> crate::Result<T>
+    where
+        T: FromStr,
+        Error: From<T::Err>,
+    {
+        self.text
+            .parse::<T>()
+            .map_err(|e| Error::from(e).relative_to(self.line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalisation() {
+        let input = PuzzleInput::new("\u{feff}a \r\nb\t\r\n\r\n\r\nc\rd\n\n  \n");
+        assert_eq!(input.as_str(), "a\nb\n\n\nc\nd");
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            ["a", "b", "", "", "c", "d"]
+        );
+        assert_eq!(
+            input.records().collect::<Vec<_>>(),
+            [
+                Record {
+                    line: 1,
+                    text: "a\nb"
+                },
+                Record {
+                    line: 5,
+                    text: "c\nd"
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use crate::bench::Bench;
+use crate::input::{PuzzleInput, Source};
+
+pub use crate::error::{Error, Result};
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod parsing;
+pub mod pool;
+pub mod scaffold;
+pub mod site;
+pub mod submissions;
+
+/// A single day's puzzle, split into parsing and the two parts so that the
+/// solutions can be driven from code as well as from the command line.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(anyhow!("Invalid part: {}", s)),
+        }
+    }
+}
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Object-safe view of a `Solution`, so that days can be dispatched on at runtime.
+pub trait Solver: Sync {
+    /// Parses `input` and solves a single part, returning the formatted answer.
+    fn solve(&self, input: &str, part: Part) -> Result<String>;
+
+    fn bench(&self, input: &str, parts: &[Part], iterations: usize) -> Result<Bench>;
+}
+impl<S: Solution + Sync> Solver for S {
+    fn solve(&self, input: &str, part: Part) -> Result<String> {
+        let input = S::parse(&PuzzleInput::new(input))?;
+        match part {
+            Part::One => S::part_one(&input).map(|answer| answer.to_string()),
+            Part::Two => S::part_two(&input).map(|answer| answer.to_string()),
+        }
+    }
+
+    fn bench(&self, input: &str, parts: &[Part], iterations: usize) -> Result<Bench> {
+        bench::bench::<S>(input, parts, iterations)
+    }
+}
+
+/// Solves both parts and prints the answers. The input is read from the path
+/// given as the first command line argument (`-` for stdin), falling back to
+/// the default input file for `day` of the event in `year`.
+pub fn run<S: Solution>(year: u16, day: u8) -> anyhow::Result<()> {
+    let source = match env::args().nth(1) {
+        Some(arg) => Source::from_arg(&arg),
+        None => Source::default_for(year, day),
+    };
+    let input = load::<S>(&source)?;
+
+    println!("Part 1: {}", S::part_one(&input)?);
+    println!("Part 2: {}", S::part_two(&input)?);
+
+    Ok(())
+}
+
+/// Reads and parses the input from `source`, rendering any parse errors
+/// against it.
+pub fn load<S: Solution>(source: &Source) -> anyhow::Result<S::Input> {
+    let raw = source.read()?;
+    S::parse(&PuzzleInput::new(&raw))
+        .map_err(|e| anyhow!("{}", e.render(&source.to_string(), &raw)))
+}
@@ -0,0 +1,156 @@
+use std::ops::{Deref, RangeInclusive};
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::is_not,
+    character::complete::{alpha1, char, digit1, one_of, space1},
+    combinator::{eof, map, map_res, opt, recognize},
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    sequence::{pair, separated_pair, terminated},
+    Finish, IResult, InputLength, Parser,
+};
+
+use crate::{Error, Result};
+
+pub type NomResult<T, U> = IResult<T, U, VerboseError<T>>;
+
+/// Runs `parser` on the whole of `input`, turning failures into parse errors
+/// at the offending column.
+pub fn nom_parse<I, O, P>(parser: P, input: I) -> Result<O>
+where
+    I: Copy + InputLength + Deref<Target = str>,
+    P: Parser<I, O, VerboseError<I>>,
+{
+    match Finish::finish(terminated(parser, eof)(input)) {
+        Ok((_, result)) => Ok(result),
+        Err(err) => {
+            let (column, message) = describe_error(&input, &err);
+            Err(Error::parse(message).at_column(column))
+        }
+    }
+}
+
+/// Describes the innermost error and the contexts it occurred in, returning
+/// the (1-based) column in `input` it occurred at along with the description.
+pub fn describe_error<I: Deref<Target = str>>(
+    input: &str,
+    err: &VerboseError<I>,
+) -> (usize, String) {
+    let (remaining, kind) = match err.errors.first() {
+        Some((remaining, kind)) => (&**remaining, kind),
+        None => return (1, "invalid input".to_string()),
+    };
+    let offset = input.len() - remaining.len();
+    let column = input[..offset].chars().count() + 1;
+
+    let found = match remaining.chars().next() {
+        Some(c) => format!("'{}'", c),
+        None => "end of input".to_string(),
+    };
+    // Prefer a context describing what was expected at the failing position
+    // over nom's generic description of the innermost parser
+    let at_position = err
+        .errors
+        .iter()
+        .take_while(|(rest, _)| rest.len() == remaining.len())
+        .find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(context.to_string()),
+            _ => None,
+        });
+    let expected = at_position.unwrap_or_else(|| match kind {
+        VerboseErrorKind::Char(c) => format!("'{}'", c),
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Nom(kind) => describe(*kind),
+    });
+    let contexts = err
+        .errors
+        .iter()
+        .skip_while(|(rest, _)| rest.len() == remaining.len())
+        .filter_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(format!(" in {}", context)),
+            _ => None,
+        })
+        .collect::<String>();
+
+    (
+        column,
+        format!("expected {}, found {}{}", expected, found, contexts),
+    )
+}
+
+/// The (1-based) column at which `part`, a slice of `line`, starts.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset)
+        .map_or(1, |before| before.chars().count() + 1)
+}
+
+fn describe(kind: ErrorKind) -> String {
+    let description = match kind {
+        ErrorKind::Alpha => "letter",
+        ErrorKind::AlphaNumeric => "letter or digit",
+        ErrorKind::Digit => "digit",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::IsNot => "value",
+        ErrorKind::MapRes => "valid value",
+        ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::Space => "space",
+        ErrorKind::Tag => "keyword",
+        kind => return kind.description().to_lowercase(),
+    };
+    description.to_string()
+}
+
+/// An unsigned decimal number.
+pub fn unsigned<T: FromStr>(input: &str) -> NomResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// A decimal number with an optional sign.
+pub fn signed<T: FromStr>(input: &str) -> NomResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// A `key:value` pair, where the value extends up to the next whitespace.
+pub fn key_value(input: &str) -> NomResult<&str, (&str, &str)> {
+    separated_pair(alpha1, char(':'), is_not(" \t"))(input)
+}
+
+/// One or more words separated by spaces.
+pub fn words(input: &str) -> NomResult<&str, Vec<&str>> {
+    separated_list1(space1, alpha1)(input)
+}
+
+/// An inclusive range written as `N-M`.
+pub fn range<T: FromStr>(input: &str) -> NomResult<&str, RangeInclusive<T>> {
+    map(
+        separated_pair(unsigned, char('-'), unsigned),
+        |(start, end)| start..=end,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(nom_parse(signed::<i32>, "-12"), Ok(-12));
+        assert_eq!(nom_parse(signed::<i32>, "+7"), Ok(7));
+        assert_eq!(nom_parse(range::<u8>, "1-3"), Ok(1..=3));
+        assert_eq!(nom_parse(key_value, "hgt:183cm"), Ok(("hgt", "183cm")));
+        assert_eq!(nom_parse(words, "light red"), Ok(vec!["light", "red"]));
+    }
+
+    #[test]
+    fn test_describe_error() {
+        assert_eq!(
+            nom_parse(range::<u8>, "1-x"),
+            Err(Error::parse("expected digit, found 'x'").at_column(3))
+        );
+
+        let line = "ab cd";
+        assert_eq!(column_of(line, &line[3..]), 4);
+    }
+}
@@ -0,0 +1,87 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The stack size of the worker threads, as much as the main thread usually
+/// gets, so that work doesn't run out of stack sooner than it would there.
+const STACK_SIZE: usize = 8 << 20;
+
+/// The number of threads to use when none is given.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Applies `f` to every item on up to `threads` threads, which take the next
+/// item as soon as they are done with one, and returns the results in the
+/// order of the items. If `f` panics for an item, its result is the panic
+/// message instead, and the other items are still processed.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            let worker = thread::Builder::new().stack_size(STACK_SIZE);
+            let spawned = worker.spawn_scoped(scope, || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(panic_message);
+                results.lock().unwrap()[index] = Some(result);
+            });
+            spawned.expect("failed to spawn a worker thread");
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let items = (0..100u64).collect::<Vec<_>>();
+        assert_eq!(
+            map(&items, 8, |&n| n * n),
+            items.iter().map(|n| Ok(n * n)).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&items, 0, |&n| n + 1)[99], Ok(100));
+        assert!(map(&[] as &[u8], 4, |&n| n).is_empty());
+
+        let results = map(&items, 4, |&n| {
+            if n % 10 == 3 {
+                panic!("no {}", n);
+            }
+            n
+        });
+        assert_eq!(results[13], Err("no 13".to_string()));
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 90);
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::days;
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+const BIN_TEMPLATE: &str = include_str!("../templates/bin.rs");
+const YEAR_TEMPLATE: &str = include_str!("../templates/year.rs");
+
+/// The module declaring the events and dispatching to their solvers.
+const DAYS_MODULE: &str = "src/days/mod.rs";
+
+/// Generates the solution module and binary for `day` of the event in `year`
+/// in the repository at `root` from the templates, registers the module with
+/// the other days of the event (adding a module for the event if it's the
+/// first day) and creates an empty `input` file unless one exists already.
+/// Returns the files that were written.
+pub fn new_day(root: &Path, year: u16, day: u8, input: &Path) -> Result<Vec<PathBuf>> {
+    if year < days::FIRST_YEAR {
+        return Err(anyhow!("Invalid year: {}", year));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("Invalid day: {}", day));
+    }
+
+    let year_path = root.join(format!("src/days/y{}/mod.rs", year));
+    let solution_path = root.join(format!("src/days/y{}/day{:02}.rs", year, day));
+    let bin_path = root.join(format!("src/bin/aoc-{}-{:02}.rs", year, day));
+    for path in [&solution_path, &bin_path] {
+        if path.exists() {
+            return Err(anyhow!("{} already exists", path.display()));
+        }
+    }
+
+    let fill = |template: &str| {
+        template
+            .replace("{{YEAR}}", &year.to_string())
+            .replace("{{NN}}", &format!("{:02}", day))
+            .replace("{{N}}", &day.to_string())
+    };
+    let mut files = vec![
+        (solution_path, fill(DAY_TEMPLATE)),
+        (bin_path, fill(BIN_TEMPLATE)),
+    ];
+    if year_path.exists() {
+        let module = read(&year_path)?;
+        files.push((year_path, register_day(&module, day)?));
+    } else {
+        let days_path = root.join(DAYS_MODULE);
+        let module = read(&days_path)?;
+        files.push((days_path, register_year(&module, year)?));
+        files.push((year_path, fill(YEAR_TEMPLATE)));
+    }
+    if !input.exists() {
+        files.push((input.to_path_buf(), String::new()));
+    }
+
+    for (path, content) in &files {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Error creating directory {}", dir.display()))?;
+        }
+        fs::write(path, content).with_context(|| format!("Error writing {}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Error reading {}", path.display()))
+}
+
+/// Adds the module declaration and solver for `day` to the module of its event.
+fn register_day(module: &str, day: u8) -> Result<String> {
+    let name = format!("day{:02}", day);
+    register(
+        module,
+        day.into(),
+        &name,
+        format!("        {} => &{}::Day{:02},", day, name, day),
+        " => &day",
+    )
+}
+
+/// Adds the module declaration and dispatch for the event in `year` to the
+/// days module.
+fn register_year(module: &str, year: u16) -> Result<String> {
+    let name = format!("y{}", year);
+    register(
+        module,
+        year,
+        &name,
+        format!("        {} => {}::solver(day),", year, name),
+        " => y",
+    )
+}
+
+/// Declares the module `name` and adds the match `arm` for `key`, keeping both
+/// ordered by key. The keys of the existing arms are found before `separator`.
+fn register(module: &str, key: u16, name: &str, arm: String, separator: &str) -> Result<String> {
+    let declaration = format!("pub mod {};", name);
+
+    let mut lines = module.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines.contains(&declaration) {
+        return Err(anyhow!("{} is already declared", name));
+    }
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    insert_sorted(&mut lines, key, declaration, |line| {
+        line.strip_prefix("pub mod ")?
+            .strip_prefix(prefix)?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })?;
+    insert_sorted(&mut lines, key, arm, |line| {
+        line.trim().split_once(separator)?.0.parse().ok()
+    })?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Inserts `line` among the lines that `key_of` finds a key in, keeping them
+/// ordered by key.
+fn insert_sorted<F>(lines: &mut Vec<String>, key: u16, line: String, key_of: F) -> Result<()>
+where
+    F: Fn(&str) -> Option<u16>,
+{
+    let keys = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, key_of(line)?)))
+        .collect::<Vec<_>>();
+    let index = match keys.iter().find(|&&(_, k)| k > key) {
+        Some(&(i, _)) => i,
+        None => match keys.last() {
+            Some(&(i, _)) => i + 1,
+            None => return Err(anyhow!("Can't find where to add {}", line.trim())),
+        },
+    };
+
+    lines.insert(index, line);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http;
+    use crate::site::{self, Config};
+
+    #[test]
+    fn test_new_then_fetch() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(
+            root.join(DAYS_MODULE),
+            "pub mod y2020;\n\nfn solver(year: u16, day: u8) {\n    match year {\n        2020 => y2020::solver(day),\n        _ => None,\n    }\n}\n",
+        )
+        .unwrap();
+        let input = root.join("input/2015/01.txt");
+
+        new_day(&root, 2015, 1, &input).unwrap();
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+
+        let (base_url, server) = http::serve(vec![(200, "(()\n")]);
+        let config = Config {
+            session: Some("abc".to_string()),
+            base_url,
+            ..Config::default()
+        };
+        assert!(site::fetch_input(&config, 2015, 1, &input).unwrap());
+        assert_eq!(fs::read_to_string(&input).unwrap(), "(()\n");
+
+        server.join().unwrap();
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_register() {
+        let module = "pub mod day01;\npub mod day13;\n\nfn solver(day: u8) {\n    match day {\n        1 => &day01::Day01,\n        13 => &day13::Day13,\n        _ => return None,\n    }\n}\n";
+        assert_eq!(
+            register_day(module, 2).unwrap(),
+            "pub mod day01;\npub mod day02;\npub mod day13;\n\nfn solver(day: u8) {\n    match day {\n        1 => &day01::Day01,\n        2 => &day02::Day02,\n        13 => &day13::Day13,\n        _ => return None,\n    }\n}\n"
+        );
+        assert!(register_day(module, 13).is_err());
+        assert!(register_day(&register_day(module, 20).unwrap(), 20).is_err());
+
+        let module = "pub mod y2020;\n\npub fn solver(year: u16, day: u8) {\n    match year {\n        2020 => y2020::solver(day),\n        _ => None,\n    }\n}\n";
+        assert_eq!(
+            register_year(module, 2015).unwrap(),
+            "pub mod y2015;\npub mod y2020;\n\npub fn solver(year: u16, day: u8) {\n    match year {\n        2015 => y2015::solver(day),\n        2020 => y2020::solver(day),\n        _ => None,\n    }\n}\n"
+        );
+    }
+}
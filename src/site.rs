@@ -0,0 +1,307 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::http;
+use crate::submissions::Outcome;
+use crate::Part;
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref LEFT_TO_WAIT: Regex =
+        Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref WAIT_MINUTES: Regex = Regex::new(r"(?i)wait (one|\d+) minutes? before").unwrap();
+}
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding where the config file is read from.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+/// Environment variable overriding the session token from the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the base URL from the config file.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Where the config is read from unless `$AOC_CONFIG` is set.
+pub const DEFAULT_CONFIG_PATH: &str = "aoc.conf";
+
+/// Settings for talking to the puzzle site, read from a config file with
+/// `key = value` lines and `#` starting a comment, and overridden by
+/// environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    /// The config file the settings were read from, or would have been if it existed.
+    pub path: PathBuf,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            path: PathBuf::from(DEFAULT_CONFIG_PATH),
+        }
+    }
+}
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = env::var_os(CONFIG_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+        let mut config = match fs::read_to_string(&path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("Error in {}", path.display()))?
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e).with_context(|| format!("Error reading {}", path.display())),
+        };
+        config.path = path;
+
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let mut config = Self::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .with_context(|| format!("line {}: Expected key = value", i + 1))?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                key => return Err(anyhow!("line {}: Unknown setting: {}", i + 1, key)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// The session token, which is checked to be safe to put into a cookie.
+    fn session(&self) -> Result<&str> {
+        let session = self.session.as_deref().with_context(|| {
+            format!(
+                "No session token; set {} or add 'session = <token>' to {}",
+                SESSION_VAR,
+                self.path.display()
+            )
+        })?;
+        let valid = !session.is_empty()
+            && session
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !matches!(c, '"' | '\\' | ';'));
+        if !valid {
+            return Err(anyhow!(
+                "Invalid session token; it can only contain printable ASCII characters \
+                 other than '\"', '\\' and ';'"
+            ));
+        }
+        Ok(session)
+    }
+
+    fn day_url(&self, year: u16, day: u8) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        )
+    }
+}
+
+/// Downloads the input for `day` of the event in `year` to `path` unless that
+/// file already exists with some content, returning whether it was
+/// downloaded. Empty files, such as the ones `aoc new` creates, are replaced.
+pub fn fetch_input(config: &Config, year: u16, day: u8, path: &Path) -> Result<bool> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(false);
+    }
+
+    let url = format!("{}/input", config.day_url(year, day));
+    let response = http::request(&url, config.session()?, None)?;
+    if !response.is_success() {
+        return Err(anyhow!(
+            "Downloading {} failed with status {}: {}",
+            url,
+            response.status,
+            response.body.lines().next().unwrap_or("")
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Error creating directory {}", dir.display()))?;
+    }
+    fs::write(path, response.body).with_context(|| format!("Error writing {}", path.display()))?;
+    Ok(true)
+}
+
+/// Submits `answer` for a part, returning the outcome, the message the
+/// site responded with and how many seconds to wait before the next answer.
+pub fn submit_answer(
+    config: &Config,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<(Outcome, String, u64)> {
+    let url = format!("{}/answer", config.day_url(year, day));
+    let level = part.to_string();
+    let response = http::request(
+        &url,
+        config.session()?,
+        Some(&[("level", &level), ("answer", answer)]),
+    )?;
+    if !response.is_success() {
+        return Err(anyhow!(
+            "Submitting to {} failed with status {}: {}",
+            url,
+            response.status,
+            response.body.lines().next().unwrap_or("")
+        ));
+    }
+
+    Ok(parse_response(&response.body))
+}
+
+fn parse_response(body: &str) -> (Outcome, String, u64) {
+    let article = ARTICLE
+        .captures(body)
+        .map_or(body, |captures| captures.get(1).unwrap().as_str());
+    let message = TAG
+        .replace_all(article, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let outcome = if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("too high") {
+        Outcome::TooHigh
+    } else if message.contains("too low") {
+        Outcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::TooSoon
+    } else if message.contains("Did you already complete it") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    };
+
+    let wait = if let Some(captures) = LEFT_TO_WAIT.captures(&message) {
+        let number = |i| {
+            captures
+                .get(i)
+                .map_or(0, |m| m.as_str().parse().unwrap_or(0))
+        };
+        number(1) * 60 + number(2)
+    } else if let Some(captures) = WAIT_MINUTES.captures(&message) {
+        match &captures[1] {
+            "one" => 60,
+            minutes => minutes.parse::<u64>().unwrap_or(1) * 60,
+        }
+    } else {
+        0
+    };
+
+    (outcome, message, wait)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse("# comment\nsession = abc\nbase_url = http://localhost:8080/\n");
+        assert_eq!(
+            config.unwrap(),
+            Config {
+                session: Some("abc".to_string()),
+                base_url: "http://localhost:8080/".to_string(),
+                ..Config::default()
+            }
+        );
+        assert_eq!(
+            Config::default().day_url(2020, 7),
+            "https://adventofcode.com/2020/day/7"
+        );
+        assert!(Config::parse("token = abc").is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let mut config = Config {
+            path: PathBuf::from("elsewhere.conf"),
+            ..Config::default()
+        };
+        let error = config.session().unwrap_err().to_string();
+        assert!(error.ends_with("to elsewhere.conf"), "{}", error);
+
+        config.session = Some("abc\"\nurl = \"x".to_string());
+        assert!(config.session().is_err());
+        config.session = Some("53616c7465645f5f".to_string());
+        assert_eq!(config.session().unwrap(), "53616c7465645f5f");
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (base_url, server) = http::serve(vec![(200, "1\n2\n")]);
+        let config = Config {
+            session: Some("abc".to_string()),
+            base_url,
+            ..Config::default()
+        };
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let path = dir.join("2020/01.txt");
+
+        assert!(fetch_input(&config, 2020, 1, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        // The stand-in only answers once, so this would fail if it requested again
+        assert!(!fetch_input(&config, 2020, 1, &path).unwrap());
+
+        let requests = server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let (outcome, message, wait) = parse_response(
+            "<main>\n<article><p>That's not the right answer; your answer is too high. \
+             <span>Please wait one minute before trying again.</span></p></article>\n</main>",
+        );
+        assert_eq!((outcome, wait), (Outcome::TooHigh, 60));
+        assert!(message.starts_with("That's not the right answer;"));
+
+        let (outcome, _, wait) = parse_response(
+            "<article><p>You gave an answer too recently; you have to wait after submitting \
+             an answer before trying again. You have 1m 5s left to wait.</p></article>",
+        );
+        assert_eq!((outcome, wait), (Outcome::TooSoon, 65));
+
+        let (outcome, _, wait) =
+            parse_response("<article><p>That's the right answer!</p></article>");
+        assert_eq!((outcome, wait), (Outcome::Correct, 0));
+    }
+}
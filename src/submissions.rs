@@ -0,0 +1,240 @@
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::Part;
+
+/// Where submitted answers are recorded.
+pub const DEFAULT_PATH: &str = "submissions.txt";
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Not checked, as the previous answer was submitted too recently.
+    TooSoon,
+    /// Not checked, as the part is already solved or not unlocked yet.
+    WrongLevel,
+    /// A response that couldn't be made sense of.
+    Unknown,
+}
+impl Outcome {
+    /// Whether the answer was checked and found to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+impl FromStr for Outcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            "too-soon" => Ok(Outcome::TooSoon),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            "unknown" => Ok(Outcome::Unknown),
+            _ => Err(anyhow!("Invalid outcome: {}", s)),
+        }
+    }
+}
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooSoon => "too-soon",
+            Outcome::WrongLevel => "wrong-level",
+            Outcome::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An answer that was sent to the site, when (in seconds since the epoch),
+/// and how long the site asked to wait before the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub time: u64,
+    pub outcome: Outcome,
+    pub wait: u64,
+    pub answer: String,
+}
+
+/// The submission history: one tab-separated `year day part time outcome
+/// wait answer` entry per line, with `#` starting a comment.
+#[derive(Debug, Default)]
+pub struct Submissions {
+    entries: Vec<Submission>,
+}
+impl Submissions {
+    /// Loads the history at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("Error in {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Error reading {}", path.display())),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| parse_entry(line).with_context(|| format!("line {}", i + 1)))
+            .collect::<Result<_>>()?;
+        Ok(Submissions { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = String::from("# year\tday\tpart\ttime\toutcome\twait\tanswer\n");
+        for entry in &self.entries {
+            writeln!(
+                content,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                entry.year,
+                entry.day,
+                entry.part,
+                entry.time,
+                entry.outcome,
+                entry.wait,
+                entry.answer
+            )?;
+        }
+        fs::write(path, content).with_context(|| format!("Error writing {}", path.display()))
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.entries.push(submission);
+    }
+
+    /// Checks whether `answer` is worth submitting at `now`, returning why not
+    /// otherwise: the part is already solved, the answer was already rejected
+    /// or is out of the bounds given by earlier too high or too low answers,
+    /// or the site's cooldown hasn't run out yet.
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str, now: u64) -> Result<()> {
+        let previous = self
+            .entries
+            .iter()
+            .filter(|e| (e.year, e.day, e.part) == (year, day, part))
+            .collect::<Vec<_>>();
+
+        if let Some(correct) = previous.iter().find(|e| e.outcome == Outcome::Correct) {
+            return Err(anyhow!("Already solved with {}", correct.answer));
+        }
+        if let Some(wrong) = previous
+            .iter()
+            .find(|e| e.answer == answer && e.outcome.is_wrong())
+        {
+            return Err(anyhow!(
+                "{} was already submitted and was {}",
+                answer,
+                wrong.outcome
+            ));
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            for entry in &previous {
+                let bound = match entry.answer.parse::<i64>() {
+                    Ok(bound) => bound,
+                    Err(_) => continue,
+                };
+                match entry.outcome {
+                    Outcome::TooHigh if value >= bound => {
+                        return Err(anyhow!("{} is too high, as {} already was", value, bound))
+                    }
+                    Outcome::TooLow if value <= bound => {
+                        return Err(anyhow!("{} is too low, as {} already was", value, bound))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // The site only accepts one answer at a time, across all puzzles
+        let ready = self.entries.iter().map(|e| e.time + e.wait).max();
+        match ready {
+            Some(ready) if ready > now => Err(anyhow!(
+                "Submitted too recently; wait another {}s",
+                ready - now
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_entry(line: &str) -> Result<Submission> {
+    let fields = line.splitn(7, '\t').collect::<Vec<_>>();
+    if fields.len() != 7 {
+        return Err(anyhow!("Expected 7 tab-separated fields: {}", line));
+    }
+    let number = |s: &str| {
+        s.parse::<u64>()
+            .with_context(|| format!("Invalid number: {}", s))
+    };
+
+    Ok(Submission {
+        year: fields[0]
+            .parse()
+            .with_context(|| format!("Invalid year: {}", fields[0]))?,
+        day: fields[1]
+            .parse()
+            .with_context(|| format!("Invalid day: {}", fields[1]))?,
+        part: fields[2].parse()?,
+        time: number(fields[3])?,
+        outcome: fields[4].parse()?,
+        wait: number(fields[5])?,
+        answer: fields[6].to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(part: Part, time: u64, outcome: Outcome, wait: u64, answer: &str) -> Submission {
+        Submission {
+            year: 2020,
+            day: 1,
+            part,
+            time,
+            outcome,
+            wait,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut history =
+            Submissions::parse("# comment\n2020\t1\t1\t100\ttoo-high\t60\t500\n").unwrap();
+        history.record(submission(Part::One, 200, Outcome::TooLow, 60, "100"));
+        history.record(submission(Part::Two, 300, Outcome::Correct, 0, "7"));
+
+        assert!(history.check(2020, 1, Part::One, "500", 400).is_err());
+        assert!(history.check(2020, 1, Part::One, "600", 400).is_err());
+        assert!(history.check(2020, 1, Part::One, "50", 400).is_err());
+        assert!(history.check(2020, 1, Part::One, "250", 400).is_ok());
+        assert!(history.check(2020, 1, Part::One, "250", 250).is_err());
+        assert!(history.check(2020, 1, Part::Two, "8", 400).is_err());
+        assert!(history.check(2020, 2, Part::One, "1", 400).is_ok());
+        assert!(history.check(2021, 1, Part::Two, "8", 400).is_ok());
+    }
+}
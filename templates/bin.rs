@@ -0,0 +1,7 @@
+use anyhow::Result;
+
+use aoc::days::y{{YEAR}}::day{{NN}}::Day{{NN}};
+
+fn main() -> Result<()> {
+    aoc::run::<Day{{NN}}>({{YEAR}}, {{N}})
+}
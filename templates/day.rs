@@ -0,0 +1,37 @@
+use crate::input::PuzzleInput;
+use crate::{Error, Result, Solution};
+
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part_one(_input: &Self::Input) -> Result<usize> {
+        Err(Error::NoSolution("Not implemented yet".to_string()))
+    }
+
+    fn part_two(_input: &Self::Input) -> Result<usize> {
+        Err(Error::NoSolution("Not implemented yet".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "add the example from the puzzle"]
+    fn test_example() {
+        let input = Day{{NN}}::parse(&PuzzleInput::new(EXAMPLE)).unwrap();
+        assert_eq!(Day{{NN}}::part_one(&input).unwrap(), 0);
+        assert_eq!(Day{{NN}}::part_two(&input).unwrap(), 0);
+    }
+}
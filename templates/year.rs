@@ -0,0 +1,12 @@
+use crate::Solver;
+
+pub mod day{{NN}};
+
+/// Looks up the solver for a day of the {{YEAR}} event, if that day has been implemented.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    let solver: &dyn Solver = match day {
+        {{N}} => &day{{NN}}::Day{{NN}},
+        _ => return None,
+    };
+    Some(solver)
+}